@@ -0,0 +1,145 @@
+//! Node and element connectivity queries.
+
+use {
+    crate::{element, mesh::Mesh, node},
+    std::collections::{BTreeMap, HashMap},
+};
+
+#[cfg(test)]
+mod test;
+
+/// Adjacency lists in compressed sparse row layout.
+///
+/// Rows are identified by the sorted ids in `ids`; the neighbours of row `i`
+/// are `indices[offsets[i]..offsets[i + 1]]`, sorted and without duplicates.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Adjacency {
+    ids: Vec<i32>,
+    offsets: Vec<usize>,
+    indices: Vec<i32>,
+}
+
+impl Adjacency {
+    pub(crate) fn new(rows: BTreeMap<i32, Vec<i32>>) -> Self {
+        let mut ids = Vec::with_capacity(rows.len());
+        let mut offsets = Vec::with_capacity(rows.len() + 1);
+        let mut indices = Vec::new();
+
+        offsets.push(0);
+        for (id, mut neighbours) in rows {
+            neighbours.sort_unstable();
+            neighbours.dedup();
+
+            ids.push(id);
+            indices.extend(neighbours);
+            offsets.push(indices.len());
+        }
+
+        Self {
+            ids,
+            offsets,
+            indices,
+        }
+    }
+
+    pub fn ids(&self) -> &[i32] {
+        &self.ids
+    }
+
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    pub fn indices(&self) -> &[i32] {
+        &self.indices
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Neighbours of `id`, `None` if it is not a row.
+    pub fn get(&self, id: i32) -> Option<&[i32]> {
+        let row = self.ids.binary_search(&id).ok()?;
+        Some(&self.indices[self.offsets[row]..self.offsets[row + 1]])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (i32, &[i32])> {
+        self.ids
+            .iter()
+            .enumerate()
+            .map(|(row, &id)| (id, &self.indices[self.offsets[row]..self.offsets[row + 1]]))
+    }
+}
+
+/// Sub-entity two elements must share to be neighbours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Connection {
+    /// Faces of volumes, edges of surfaces, vertices of lines.
+    Facet,
+    Edge,
+    Node,
+}
+
+impl Mesh {
+    /// Elements incident to each node.
+    pub fn node_elements(&self) -> Adjacency {
+        let mut rows: BTreeMap<node::Id, Vec<element::Id>> = BTreeMap::new();
+        for (id, (_, _, topology)) in self.elements.iter() {
+            for node in topology.nodes() {
+                rows.entry(node).or_default().push(*id);
+            }
+        }
+        Adjacency::new(rows)
+    }
+
+    /// Elements sharing a `connection` with each element.
+    ///
+    /// Only elements of the same dimension are considered neighbours, so that
+    /// tetrahedra are not connected to the boundary triangles lying on them.
+    pub fn element_neighbours(&self, connection: Connection) -> Adjacency {
+        let mut shared: HashMap<(i32, Vec<node::Id>), Vec<element::Id>> = HashMap::new();
+        let mut rows: BTreeMap<element::Id, Vec<element::Id>> = BTreeMap::new();
+        for (id, (_, _, topology)) in self.elements.iter() {
+            let entities = match connection {
+                Connection::Facet => topology.facets(),
+                Connection::Edge => topology.edges().into_iter().map(Vec::from).collect(),
+                Connection::Node => topology.nodes().into_iter().map(|n| vec![n]).collect(),
+            };
+            for mut entity in entities {
+                entity.sort_unstable();
+                shared
+                    .entry((topology.dimension(), entity))
+                    .or_default()
+                    .push(*id);
+            }
+            rows.insert(*id, vec![]);
+        }
+
+        for elements in shared.values() {
+            for a in elements.iter() {
+                let neighbours = rows.entry(*a).or_default();
+                neighbours.extend(elements.iter().filter(|b| *b != a));
+            }
+        }
+        Adjacency::new(rows)
+    }
+
+    /// Nodes sharing an element with each node, i.e. the sparsity pattern of
+    /// a nodal finite element operator without its diagonal.
+    pub fn node_graph(&self) -> Adjacency {
+        let mut rows: BTreeMap<node::Id, Vec<node::Id>> = BTreeMap::new();
+        for (_, _, topology) in self.elements.values() {
+            let nodes = topology.nodes();
+            for a in nodes.iter() {
+                let neighbours = rows.entry(*a).or_default();
+                neighbours.extend(nodes.iter().filter(|b| *b != a));
+            }
+        }
+        Adjacency::new(rows)
+    }
+}
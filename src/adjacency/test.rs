@@ -0,0 +1,66 @@
+use {
+    super::Connection,
+    crate::{
+        element::Topology,
+        mesh::{self, Mesh},
+        node::Node,
+    },
+};
+
+// Two tetrahedra sharing the face (2, 3, 4), a third one touching the
+// second through the edge (3, 5) and a boundary triangle on the first.
+fn mesh() -> Mesh {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(0.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 0.0, 1.0));
+    ns.insert(5, Node::new(1.0, 1.0, 1.0));
+    ns.insert(6, Node::new(2.0, 2.0, 0.0));
+    ns.insert(7, Node::new(2.0, 2.0, 2.0));
+
+    let mut es = mesh::Elements::new();
+    es.insert(10, (1, 1, Topology::Tetrahedron4(1, 2, 3, 4)));
+    es.insert(20, (1, 1, Topology::Tetrahedron4(2, 3, 4, 5)));
+    es.insert(30, (1, 1, Topology::Tetrahedron4(3, 5, 6, 7)));
+    es.insert(40, (2, 2, Topology::Triangle3(1, 2, 3)));
+
+    Mesh::new(None, ns, es)
+}
+
+#[test]
+fn node_elements() {
+    let adjacency = mesh().node_elements();
+
+    assert_eq!(adjacency.ids(), &[1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(adjacency.get(1), Some(&[10, 40][..]));
+    assert_eq!(adjacency.get(3), Some(&[10, 20, 30, 40][..]));
+    assert_eq!(adjacency.get(8), None);
+    assert_eq!(adjacency.offsets().len(), adjacency.len() + 1);
+}
+
+#[test]
+fn element_neighbours() {
+    let mesh = mesh();
+
+    let facets = mesh.element_neighbours(Connection::Facet);
+    assert_eq!(facets.get(10), Some(&[20][..]));
+    assert_eq!(facets.get(20), Some(&[10][..]));
+    assert_eq!(facets.get(30), Some(&[][..]));
+    assert_eq!(facets.get(40), Some(&[][..]));
+
+    let edges = mesh.element_neighbours(Connection::Edge);
+    assert_eq!(edges.get(20), Some(&[10, 30][..]));
+
+    let nodes = mesh.element_neighbours(Connection::Node);
+    assert_eq!(nodes.get(10), Some(&[20, 30][..]));
+}
+
+#[test]
+fn node_graph() {
+    let graph = mesh().node_graph();
+
+    assert_eq!(graph.get(1), Some(&[2, 3, 4][..]));
+    assert_eq!(graph.get(3), Some(&[1, 2, 4, 5, 6, 7][..]));
+    assert_eq!(graph.get(7), Some(&[3, 5, 6][..]));
+}
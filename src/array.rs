@@ -0,0 +1,73 @@
+//! Conversions between meshes and the arrays of numeric crates.
+
+use crate::{
+    element::{self, Family, Topology},
+    encode,
+    mesh::{self, Mesh},
+    node::{self, Node},
+};
+
+#[cfg(test)]
+mod test;
+
+#[cfg(feature = "nalgebra")]
+pub(crate) mod nalgebra;
+#[cfg(feature = "ndarray")]
+pub(crate) mod ndarray;
+
+/// Node ids of `mesh` in id order, with their coordinates.
+pub(crate) fn points(mesh: &Mesh) -> (Vec<node::Id>, Vec<[f64; 3]>) {
+    encode::nodes(mesh)
+        .into_iter()
+        .map(|(id, node)| (id, node.coordinates()))
+        .unzip()
+}
+
+/// Ids of the elements of `family` in id order, with their nodes as
+/// zero-based indices into the nodes in id order, row after row.
+///
+/// Returns `None` if an element refers to a missing node.
+pub(crate) fn connectivity(mesh: &Mesh, family: Family) -> Option<(Vec<element::Id>, Vec<usize>)> {
+    let indices = encode::indices(&encode::nodes(mesh));
+    let mut ids = Vec::new();
+    let mut rows = Vec::new();
+    for (id, .., topology) in encode::elements(mesh) {
+        if topology.family() == family {
+            ids.push(id);
+            for id in topology.nodes() {
+                rows.push(*indices.get(&id)?);
+            }
+        }
+    }
+    Some((ids, rows))
+}
+
+/// Mesh over `points`, numbered from 1, with the elements of each family in
+/// turn, given as rows of zero-based node indices and numbered from 1.
+///
+/// Elements are untagged. Returns `None` if a row does not match its family
+/// or refers to a missing point.
+pub(crate) fn mesh<I: IntoIterator<Item = [f64; 3]>>(
+    points: I,
+    cells: &[(Family, Vec<usize>)],
+) -> Option<Mesh> {
+    let nodes: mesh::Nodes = (1..)
+        .zip(points)
+        .map(|(id, p)| (id, Node::new(p[0], p[1], p[2])))
+        .collect();
+    let mut elements = mesh::Elements::new();
+    for (family, rows) in cells {
+        if !rows.len().is_multiple_of(family.vertices()) {
+            return None;
+        }
+        for row in rows.chunks(family.vertices()) {
+            if row.iter().any(|&index| index >= nodes.len()) {
+                return None;
+            }
+            let ids: Vec<node::Id> = row.iter().map(|&index| index as node::Id + 1).collect();
+            let id = elements.len() as element::Id + 1;
+            elements.insert(id, (0, 0, Topology::new(*family, &ids)?));
+        }
+    }
+    Some(Mesh::new(None, nodes, elements))
+}
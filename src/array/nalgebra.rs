@@ -0,0 +1,50 @@
+//! `nalgebra` matrices, with a column per node and a row per element.
+
+use {
+    crate::{array, element, mesh::Mesh, node, Family},
+    nalgebra::{DMatrix, Matrix3xX},
+};
+
+impl Mesh {
+    /// Node ids in id order, with their coordinates as the columns of a
+    /// `3 × n` matrix.
+    pub fn coordinates_matrix(&self) -> (Vec<node::Id>, Matrix3xX<f64>) {
+        let (ids, points) = array::points(self);
+        let coordinates = Matrix3xX::from_iterator(points.len(), points.into_iter().flatten());
+        (ids, coordinates)
+    }
+
+    /// Ids of the elements of `family` in id order, with their nodes as the
+    /// rows of a matrix of indices into [`Mesh::coordinates_matrix`].
+    ///
+    /// Returns `None` if one of these elements refers to a missing node.
+    pub fn connectivity_matrix(
+        &self,
+        family: Family,
+    ) -> Option<(Vec<element::Id>, DMatrix<usize>)> {
+        let (ids, rows) = array::connectivity(self, family)?;
+        let connectivity = DMatrix::from_row_slice(ids.len(), family.vertices(), &rows);
+        Some((ids, connectivity))
+    }
+
+    /// Untagged mesh over the columns of `coordinates`, with the elements of
+    /// each family in `connectivity` given as rows of zero-based indices into
+    /// them. Nodes and elements are numbered from 1 in order.
+    ///
+    /// Returns `None` if a connectivity matrix does not have as many columns
+    /// as its family has nodes, or if an index is out of range.
+    pub fn from_matrices(
+        coordinates: &Matrix3xX<f64>,
+        connectivity: &[(Family, DMatrix<usize>)],
+    ) -> Option<Self> {
+        let points = coordinates.column_iter().map(|c| [c[0], c[1], c[2]]);
+        let cells: Option<Vec<(Family, Vec<usize>)>> = connectivity
+            .iter()
+            .map(|(family, rows)| {
+                let fits = rows.ncols() == family.vertices() || rows.nrows() == 0;
+                fits.then(|| (*family, rows.transpose().iter().copied().collect()))
+            })
+            .collect();
+        array::mesh(points, &cells?)
+    }
+}
@@ -0,0 +1,52 @@
+//! `ndarray` arrays, with a row per node or element.
+
+use {
+    crate::{array, element, mesh::Mesh, node, Family},
+    ndarray::{Array2, ArrayView2},
+};
+
+impl Mesh {
+    /// Node ids in id order, with their coordinates as the rows of an
+    /// `n × 3` array.
+    pub fn coordinates_array(&self) -> (Vec<node::Id>, Array2<f64>) {
+        let (ids, points) = array::points(self);
+        let coordinates = Array2::from(points);
+        (ids, coordinates)
+    }
+
+    /// Ids of the elements of `family` in id order, with their nodes as the
+    /// rows of an array of indices into [`Mesh::coordinates_array`].
+    ///
+    /// Returns `None` if one of these elements refers to a missing node.
+    pub fn connectivity_array(&self, family: Family) -> Option<(Vec<element::Id>, Array2<usize>)> {
+        let (ids, rows) = array::connectivity(self, family)?;
+        let shape = (ids.len(), family.vertices());
+        let connectivity = Array2::from_shape_vec(shape, rows).ok()?;
+        Some((ids, connectivity))
+    }
+
+    /// Untagged mesh over the rows of `coordinates`, with the elements of
+    /// each family in `connectivity` given as rows of zero-based indices into
+    /// them. Nodes and elements are numbered from 1 in order.
+    ///
+    /// Returns `None` if `coordinates` does not have 3 columns, or a
+    /// connectivity array as many columns as its family has nodes, or if an
+    /// index is out of range.
+    pub fn from_arrays(
+        coordinates: ArrayView2<f64>,
+        connectivity: &[(Family, ArrayView2<usize>)],
+    ) -> Option<Self> {
+        if coordinates.ncols() != 3 {
+            return None;
+        }
+        let points = coordinates.rows().into_iter().map(|r| [r[0], r[1], r[2]]);
+        let cells: Option<Vec<(Family, Vec<usize>)>> = connectivity
+            .iter()
+            .map(|(family, rows)| {
+                let fits = rows.ncols() == family.vertices() || rows.nrows() == 0;
+                fits.then(|| (*family, rows.iter().copied().collect()))
+            })
+            .collect();
+        array::mesh(points, &cells?)
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{
+    element::{Family, Topology},
+    mesh::{self, Mesh},
+    node::Node,
+};
+
+// Unit square split into two triangles, with an edge on its boundary and
+// sparse ids.
+fn square() -> Mesh {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(1.0, 1.0, 0.0));
+    ns.insert(5, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 10, Topology::Triangle3(1, 2, 3)));
+    es.insert(2, (1, 10, Topology::Triangle3(1, 3, 5)));
+    es.insert(7, (2, 20, Topology::Line2(1, 2)));
+    Mesh::new(None, ns, es)
+}
+
+// The square renumbered from 1 and untagged.
+fn untagged() -> Mesh {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(1.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (0, 0, Topology::Triangle3(1, 2, 3)));
+    es.insert(2, (0, 0, Topology::Triangle3(1, 3, 4)));
+    es.insert(3, (0, 0, Topology::Line2(1, 2)));
+    Mesh::new(None, ns, es)
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn ndarray() {
+    use ndarray::array;
+
+    let mesh = square();
+    let (ids, coordinates) = mesh.coordinates_array();
+    assert_eq!(ids, [1, 2, 3, 5]);
+    assert_eq!(
+        coordinates,
+        array![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0]
+        ]
+    );
+    let (ids, triangles) = mesh.connectivity_array(Family::Triangle).unwrap();
+    assert_eq!(ids, [1, 2]);
+    assert_eq!(triangles, array![[0, 1, 2], [0, 2, 3]]);
+    let (ids, lines) = mesh.connectivity_array(Family::Line).unwrap();
+    assert_eq!(ids, [7]);
+    assert_eq!(lines, array![[0, 1]]);
+    let (ids, tetrahedra) = mesh.connectivity_array(Family::Tetrahedron).unwrap();
+    assert_eq!((ids.len(), tetrahedra.dim()), (0, (0, 4)));
+
+    let mut over = mesh.clone();
+    over.elements
+        .insert(8, (1, 10, Topology::Triangle3(1, 2, 4)));
+    assert_eq!(over.connectivity_array(Family::Triangle), None);
+    assert!(over.connectivity_array(Family::Line).is_some());
+
+    let cells = [
+        (Family::Triangle, triangles.view()),
+        (Family::Line, lines.view()),
+    ];
+    let built = Mesh::from_arrays(coordinates.view(), &cells).unwrap();
+    assert_eq!(built, untagged());
+
+    let outside = array![[0, 1, 4]];
+    let cells = [(Family::Triangle, outside.view())];
+    assert_eq!(Mesh::from_arrays(coordinates.view(), &cells), None);
+    let cells = [(Family::Quadrangle, triangles.view())];
+    assert_eq!(Mesh::from_arrays(coordinates.view(), &cells), None);
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn nalgebra() {
+    use nalgebra::{DMatrix, Matrix3xX};
+
+    let mesh = square();
+    let (ids, coordinates) = mesh.coordinates_matrix();
+    assert_eq!(ids, [1, 2, 3, 5]);
+    let expected =
+        Matrix3xX::from_column_slice(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0]);
+    assert_eq!(coordinates, expected);
+    let (ids, triangles) = mesh.connectivity_matrix(Family::Triangle).unwrap();
+    assert_eq!(ids, [1, 2]);
+    assert_eq!(
+        triangles,
+        DMatrix::from_row_slice(2, 3, &[0, 1, 2, 0, 2, 3])
+    );
+    let (_, lines) = mesh.connectivity_matrix(Family::Line).unwrap();
+
+    let cells = [(Family::Triangle, triangles), (Family::Line, lines)];
+    let built = Mesh::from_matrices(&coordinates, &cells).unwrap();
+    assert_eq!(built, untagged());
+
+    let cells = [(Family::Line, DMatrix::from_row_slice(1, 2, &[0, 4]))];
+    assert_eq!(Mesh::from_matrices(&coordinates, &cells), None);
+}
@@ -0,0 +1,116 @@
+//! Boundary facets of the highest dimensional elements of a mesh.
+
+use {
+    crate::{
+        element::{self, Elementary, Family, Physical, Topology},
+        mesh::Mesh,
+        node,
+    },
+    std::collections::HashMap,
+};
+
+#[cfg(test)]
+mod test;
+
+/// Element, local index and nodes of a facet.
+type Owner = (element::Id, usize, Vec<node::Id>);
+
+/// Facet owned by a single element, oriented as in its owner.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Facet {
+    topology: Topology,
+    element: element::Id,
+    index: usize,
+    existing: Option<(element::Id, Physical, Elementary)>,
+}
+
+impl Facet {
+    pub fn topology(&self) -> &Topology {
+        &self.topology
+    }
+
+    /// Owning element.
+    pub fn element(&self) -> element::Id {
+        self.element
+    }
+
+    /// Local index of the facet in its owner, following `Topology::facets`.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Lower dimensional element of the mesh covering the same nodes, if any;
+    /// the one with the lowest id when several do.
+    pub fn existing(&self) -> Option<element::Id> {
+        self.existing.map(|(id, _, _)| id)
+    }
+
+    /// Physical tag of the covering element, if any.
+    pub fn physical(&self) -> Option<Physical> {
+        self.existing.map(|(_, physical, _)| physical)
+    }
+
+    /// Elementary tag of the covering element, if any.
+    pub fn elementary(&self) -> Option<Elementary> {
+        self.existing.map(|(_, _, elementary)| elementary)
+    }
+}
+
+impl Mesh {
+    /// Facets of the highest dimensional elements not shared with another
+    /// element: faces of volume meshes and edges of surface meshes.
+    ///
+    /// Facets are sorted by owning element and local index.
+    pub fn boundary(&self) -> Vec<Facet> {
+        let dimension = match self.elements.values().map(|(_, _, t)| t.dimension()).max() {
+            Some(dimension) if dimension > 0 => dimension,
+            _ => return vec![],
+        };
+
+        let mut owners: HashMap<Vec<node::Id>, Vec<Owner>> = HashMap::new();
+        let mut existing: HashMap<Vec<node::Id>, (element::Id, Physical, Elementary)> =
+            HashMap::new();
+        for (id, (physical, elementary, topology)) in self.elements.iter() {
+            if topology.dimension() == dimension - 1 {
+                let mut key = topology.nodes();
+                key.sort_unstable();
+                // Duplicated elements resolve to the lowest id.
+                existing
+                    .entry(key)
+                    .and_modify(|e| *e = (*e).min((*id, *physical, *elementary)))
+                    .or_insert((*id, *physical, *elementary));
+            }
+            if topology.dimension() != dimension {
+                continue;
+            }
+            for (index, facet) in topology.facets().into_iter().enumerate() {
+                let mut key = facet.clone();
+                key.sort_unstable();
+                owners.entry(key).or_default().push((*id, index, facet));
+            }
+        }
+
+        let mut facets: Vec<Facet> = owners
+            .into_iter()
+            .filter(|(_, owners)| owners.len() == 1)
+            .filter_map(|(key, mut owners)| {
+                let (element, index, nodes) = owners.pop()?;
+                let family = match nodes.len() {
+                    1 => Family::Point,
+                    2 => Family::Line,
+                    3 => Family::Triangle,
+                    4 => Family::Quadrangle,
+                    _ => return None,
+                };
+                Some(Facet {
+                    topology: Topology::new(family, &nodes)?,
+                    element,
+                    index,
+                    existing: existing.get(&key).copied(),
+                })
+            })
+            .collect();
+        facets.sort_by_key(|f| (f.element, f.index));
+        facets
+    }
+}
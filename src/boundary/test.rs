@@ -0,0 +1,73 @@
+use crate::{
+    element::Topology,
+    mesh::{self, Mesh},
+    node::Node,
+};
+
+#[test]
+fn tetrahedra() {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(0.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 0.0, 1.0));
+    ns.insert(5, Node::new(1.0, 1.0, 1.0));
+
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Tetrahedron4(1, 2, 3, 4)));
+    es.insert(2, (1, 1, Topology::Tetrahedron4(2, 3, 4, 5)));
+    es.insert(3, (7, 2, Topology::Triangle3(1, 2, 3)));
+    for id in 4..12 {
+        es.insert(id, (8, 3, Topology::Triangle3(3, 2, 1)));
+    }
+
+    let boundary = Mesh::new(None, ns, es).boundary();
+    assert_eq!(boundary.len(), 6);
+    assert!(boundary.iter().all(|f| f.element() != 2 || f.index() != 0));
+
+    let bottom = &boundary[0];
+    assert_eq!(bottom.element(), 1);
+    assert_eq!(bottom.index(), 0);
+    assert_eq!(bottom.topology(), &Topology::Triangle3(1, 3, 2));
+    assert_eq!(bottom.existing(), Some(3));
+    assert_eq!(bottom.physical(), Some(7));
+    assert_eq!(bottom.elementary(), Some(2));
+
+    assert!(boundary[1..].iter().all(|f| f.existing().is_none()));
+}
+
+#[test]
+fn quadrangles() {
+    let mut ns = mesh::Nodes::new();
+    for (id, (x, y)) in [
+        (0.0, 0.0),
+        (1.0, 0.0),
+        (2.0, 0.0),
+        (0.0, 1.0),
+        (1.0, 1.0),
+        (2.0, 1.0),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        ns.insert(id as i32 + 1, Node::new(x, y, 0.0));
+    }
+
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Quadrangle4(1, 2, 5, 4)));
+    es.insert(2, (1, 1, Topology::Quadrangle4(2, 3, 6, 5)));
+
+    let boundary = Mesh::new(None, ns, es).boundary();
+    let edges: Vec<Topology> = boundary.iter().map(|f| *f.topology()).collect();
+    assert_eq!(
+        edges,
+        vec![
+            Topology::Line2(1, 2),
+            Topology::Line2(5, 4),
+            Topology::Line2(4, 1),
+            Topology::Line2(2, 3),
+            Topology::Line2(3, 6),
+            Topology::Line2(6, 5),
+        ]
+    );
+}
@@ -0,0 +1,219 @@
+//! Construction of meshes element by element.
+
+use {
+    crate::{
+        element::{self, Elementary, Physical, Topology},
+        format::Format,
+        mesh::{self, Mesh},
+        node::{self, Node},
+    },
+    std::collections::{BTreeMap, HashSet},
+};
+
+#[cfg(test)]
+mod test;
+
+/// Reason for [`MeshBuilder::build`] to reject a mesh.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Invalid {
+    /// A node id given more than once.
+    DuplicateNode(node::Id),
+    /// An element id given more than once.
+    DuplicateElement(element::Id),
+    /// An element over a node that was never added.
+    MissingNode(element::Id, node::Id),
+    /// A physical name of a dimension outside 0 to 3, with its tag.
+    PhysicalName(i32, Physical),
+    /// An id or entity tag to assign above `i32::MAX`.
+    Overflow,
+}
+
+impl std::fmt::Display for Invalid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateNode(id) => write!(f, "node {id} added more than once"),
+            Self::DuplicateElement(id) => write!(f, "element {id} added more than once"),
+            Self::MissingNode(id, node) => write!(f, "element {id} over missing node {node}"),
+            Self::PhysicalName(dimension, tag) => {
+                write!(f, "physical name {tag} of invalid dimension {dimension}")
+            }
+            Self::Overflow => write!(f, "ran out of ids or entity tags"),
+        }
+    }
+}
+
+impl std::error::Error for Invalid {}
+
+/// Mesh under construction, assigning ids one above the highest so far.
+///
+/// References are only checked by [`MeshBuilder::build`], so nodes and
+/// elements can be added in any order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshBuilder {
+    format: Option<Format>,
+    nodes: mesh::Nodes,
+    elements: mesh::Elements,
+    physical_names: mesh::PhysicalNames,
+    problems: Vec<Invalid>,
+    node: node::Id,
+    element: element::Id,
+    elementary: Elementary,
+}
+
+impl MeshBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// One above `last`, or `None` once ids run out, as reported by `build`.
+    fn after(&mut self, last: i32) -> Option<i32> {
+        let next = last.checked_add(1);
+        if next.is_none() {
+            self.problems.push(Invalid::Overflow);
+        }
+        next
+    }
+
+    /// Adds `node`, returning its id; past `i32::MAX` the node is dropped
+    /// and `build` fails.
+    pub fn add_node(&mut self, node: Node) -> node::Id {
+        let Some(id) = self.after(self.node) else {
+            return self.node;
+        };
+        self.add_node_with_id(id, node);
+        id
+    }
+
+    /// Adds `node` as `id`; ids given twice are reported by `build`.
+    pub fn add_node_with_id(&mut self, id: node::Id, node: Node) -> &mut Self {
+        if self.nodes.insert(id, node).is_some() {
+            self.problems.push(Invalid::DuplicateNode(id));
+        }
+        self.node = self.node.max(id);
+        self
+    }
+
+    /// Adds an element over `topology` with the given tags, returning its id;
+    /// past `i32::MAX` the element is dropped and `build` fails.
+    pub fn add_element(
+        &mut self,
+        topology: Topology,
+        physical: Physical,
+        elementary: Elementary,
+    ) -> element::Id {
+        let Some(id) = self.after(self.element) else {
+            return self.element;
+        };
+        self.add_element_with_id(id, topology, physical, elementary);
+        id
+    }
+
+    /// Adds an element as `id`; ids given twice are reported by `build`.
+    pub fn add_element_with_id(
+        &mut self,
+        id: element::Id,
+        topology: Topology,
+        physical: Physical,
+        elementary: Elementary,
+    ) -> &mut Self {
+        if self
+            .elements
+            .insert(id, (physical, elementary, topology))
+            .is_some()
+        {
+            self.problems.push(Invalid::DuplicateElement(id));
+        }
+        self.element = self.element.max(id);
+        self.elementary = self.elementary.max(elementary);
+        self
+    }
+
+    /// Names the physical group of `dimension` and `tag`.
+    pub fn add_physical_name<S: Into<String>>(
+        &mut self,
+        dimension: i32,
+        tag: Physical,
+        name: S,
+    ) -> &mut Self {
+        self.physical_names.insert((dimension, tag), name.into());
+        self
+    }
+
+    /// Adds `topologies` as the elements of a new elementary entity in the
+    /// `physical` group, returning the entity tag and the element ids.
+    pub fn add_entity<I: IntoIterator<Item = Topology>>(
+        &mut self,
+        physical: Physical,
+        topologies: I,
+    ) -> (Elementary, Vec<element::Id>) {
+        let elementary = self.after(self.elementary).unwrap_or(self.elementary);
+        self.elementary = elementary;
+        let ids = topologies
+            .into_iter()
+            .map(|topology| self.add_element(topology, physical, elementary))
+            .collect();
+        (elementary, ids)
+    }
+
+    /// Moves the `elements` to a partition, as gmsh does: the elements of
+    /// each entity go to a new entity, keeping their physical tag.
+    ///
+    /// Returns the parent and new entity tags, by parent. Unknown element
+    /// ids are skipped.
+    pub fn add_partition(&mut self, elements: &[element::Id]) -> Vec<(Elementary, Elementary)> {
+        let mut entities = BTreeMap::new();
+        for id in elements {
+            let Some((_, elementary, _)) = self.elements.get(id) else {
+                continue;
+            };
+            let parent = *elementary;
+            let partitioned = match entities.get(&parent) {
+                Some(&partitioned) => partitioned,
+                None => {
+                    let next = self.after(self.elementary).unwrap_or(self.elementary);
+                    self.elementary = next;
+                    entities.insert(parent, next);
+                    next
+                }
+            };
+            if let Some((_, elementary, _)) = self.elements.get_mut(id) {
+                *elementary = partitioned;
+            }
+        }
+        entities.into_iter().collect()
+    }
+
+    /// The mesh, once every element refers to added nodes, no id was given
+    /// twice and every physical name has a dimension from 0 to 3.
+    ///
+    /// Reports the first problem found, duplicates and overflows first, then
+    /// elements and names by increasing id.
+    pub fn build(self) -> Result<Mesh, Invalid> {
+        if let Some(invalid) = self.problems.into_iter().next() {
+            return Err(invalid);
+        }
+        let mut ids: Vec<&element::Id> = self.elements.keys().collect();
+        ids.sort_unstable();
+        let mut seen = HashSet::new();
+        for id in ids {
+            let (.., topology) = &self.elements[id];
+            for node in topology.nodes() {
+                if seen.insert(node) && !self.nodes.contains_key(&node) {
+                    return Err(Invalid::MissingNode(*id, node));
+                }
+            }
+        }
+        let mut names: Vec<&(i32, Physical)> = self.physical_names.keys().collect();
+        names.sort_unstable();
+        if let Some(&&(dimension, tag)) = names.iter().find(|(d, _)| !(0..=3).contains(d)) {
+            return Err(Invalid::PhysicalName(dimension, tag));
+        }
+        Ok(Mesh::new(self.format, self.nodes, self.elements)
+            .with_physical_names(self.physical_names))
+    }
+}
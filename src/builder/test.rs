@@ -0,0 +1,95 @@
+use crate::{
+    builder::{Invalid, MeshBuilder},
+    element::Topology,
+    format::Format,
+    mesh::{self, Mesh},
+    node::Node,
+};
+
+// Unit square split into two triangles, with an edge on its boundary.
+fn square() -> MeshBuilder {
+    let mut builder = MeshBuilder::new();
+    let a = builder.add_node(Node::new(0.0, 0.0, 0.0));
+    let b = builder.add_node(Node::new(1.0, 0.0, 0.0));
+    let c = builder.add_node(Node::new(1.0, 1.0, 0.0));
+    let d = builder.add_node(Node::new(0.0, 1.0, 0.0));
+    builder.add_entity(
+        1,
+        [Topology::Triangle3(a, b, c), Topology::Triangle3(a, c, d)],
+    );
+    builder.add_element(Topology::Line2(a, b), 2, 20);
+    builder.add_physical_name(2, 1, "plate");
+    builder
+}
+
+#[test]
+fn build() {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(1.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Triangle3(1, 2, 3)));
+    es.insert(2, (1, 1, Topology::Triangle3(1, 3, 4)));
+    es.insert(3, (2, 20, Topology::Line2(1, 2)));
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((2, 1), String::from("plate"));
+    let expected = Mesh::new(None, ns, es).with_physical_names(names);
+    assert_eq!(square().build(), Ok(expected));
+
+    // Ids continue above the highest given, entities above the highest tag.
+    let mut builder = MeshBuilder::new().with_format(Format::new(2.2, 0, 8));
+    builder.add_node_with_id(10, Node::new(0.0, 0.0, 0.0));
+    assert_eq!(builder.add_node(Node::new(1.0, 0.0, 0.0)), 11);
+    builder.add_element_with_id(5, Topology::Line2(10, 11), 0, 7);
+    assert_eq!(builder.add_entity(0, [Topology::Point1(10)]), (8, vec![6]));
+    let mesh = builder.build().unwrap();
+    assert_eq!(mesh.elements()[&6], (0, 8, Topology::Point1(10)));
+}
+
+#[test]
+fn partition() {
+    let mut builder = square();
+    assert_eq!(builder.add_partition(&[2, 3, 9]), [(1, 21), (20, 22)]);
+    let mesh = builder.build().unwrap();
+    assert_eq!(mesh.elements()[&1].1, 1);
+    assert_eq!(mesh.elements()[&2], (1, 21, Topology::Triangle3(1, 3, 4)));
+    assert_eq!(mesh.elements()[&3].1, 22);
+}
+
+#[test]
+fn invalid() {
+    let mut builder = square();
+    builder.add_element(Topology::Line2(4, 9), 0, 0);
+    assert_eq!(builder.build(), Err(Invalid::MissingNode(4, 9)));
+
+    let mut builder = square();
+    builder.add_node_with_id(2, Node::new(2.0, 0.0, 0.0));
+    assert_eq!(builder.build(), Err(Invalid::DuplicateNode(2)));
+
+    let mut builder = square();
+    builder.add_element_with_id(1, Topology::Point1(1), 0, 0);
+    assert_eq!(builder.build(), Err(Invalid::DuplicateElement(1)));
+
+    let mut builder = square();
+    builder.add_physical_name(4, 1, "hyper");
+    let invalid = builder.build().unwrap_err();
+    assert_eq!(invalid, Invalid::PhysicalName(4, 1));
+    assert_eq!(
+        invalid.to_string(),
+        "physical name 1 of invalid dimension 4"
+    );
+
+    let mut builder = square();
+    builder.add_node_with_id(i32::MAX, Node::new(2.0, 0.0, 0.0));
+    builder.add_node(Node::new(3.0, 0.0, 0.0));
+    assert_eq!(builder.nodes.len(), 5);
+    assert_eq!(builder.build(), Err(Invalid::Overflow));
+
+    let mut builder = square();
+    builder.add_element_with_id(i32::MAX, Topology::Point1(1), 0, i32::MAX);
+    builder.add_element(Topology::Point1(2), 0, 0);
+    builder.add_entity(0, [Topology::Point1(3)]);
+    assert_eq!(builder.build(), Err(Invalid::Overflow));
+}
@@ -0,0 +1,111 @@
+//! Values attached to nodes or elements, as in the gmsh `$NodeData` and
+//! `$ElementData` sections.
+
+use {
+    crate::{mesh::Mesh, node},
+    std::collections::HashMap,
+};
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Data {
+    name: String,
+    step: i32,
+    time: f64,
+    components: usize,
+    values: HashMap<i32, Vec<f64>>,
+}
+
+impl Data {
+    /// Empty data named `name`, with `components` values per id.
+    pub fn new<S: Into<String>>(name: S, components: usize) -> Self {
+        Self {
+            name: name.into(),
+            step: 0,
+            time: 0.0,
+            components,
+            values: HashMap::new(),
+        }
+    }
+
+    pub fn with_time(mut self, step: i32, time: f64) -> Self {
+        self.step = step;
+        self.time = time;
+        self
+    }
+
+    /// Sets the values of node or element `id`, returning the previous ones.
+    ///
+    /// Gives `values` back as the error if it does not hold exactly
+    /// `components` values.
+    pub fn insert(&mut self, id: i32, values: Vec<f64>) -> Result<Option<Vec<f64>>, Vec<f64>> {
+        if values.len() != self.components {
+            return Err(values);
+        }
+        Ok(self.values.insert(id, values))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn step(&self) -> i32 {
+        self.step
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    pub fn get(&self, id: i32) -> Option<&[f64]> {
+        self.values.get(&id).map(Vec::as_slice)
+    }
+
+    pub fn values(&self) -> &HashMap<i32, Vec<f64>> {
+        &self.values
+    }
+}
+
+impl Mesh {
+    /// Nodal `data` of this mesh interpolated at the nodes of `target`, with
+    /// the shape functions of the element containing each target node, or of
+    /// the nearest element for nodes outside this mesh.
+    ///
+    /// Target nodes are left without values when that element has a node
+    /// without values in `data`.
+    pub fn interpolate(&self, data: &Data, target: &Mesh) -> Data {
+        let locator = self.locator();
+        let mut interpolated =
+            Data::new(data.name(), data.components()).with_time(data.step(), data.time());
+
+        for (&id, node) in &target.nodes {
+            let Some(location) = locator.nearest(node.coordinates()) else {
+                continue;
+            };
+            let (_, _, topology) = &self.elements[&location.element()];
+            let values: Option<Vec<&[f64]>> = topology
+                .nodes()
+                .into_iter()
+                .map(|n: node::Id| data.get(n))
+                .collect();
+            let Some(values) = values else {
+                continue;
+            };
+
+            let mut value = vec![0.0; data.components()];
+            for (weight, v) in location.weights().iter().zip(values) {
+                for (x, y) in value.iter_mut().zip(v) {
+                    *x += weight * y;
+                }
+            }
+            interpolated.values.insert(id, value);
+        }
+        interpolated
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    data::Data,
+    element::Topology,
+    mesh::{self, Mesh},
+    node::Node,
+};
+
+fn linear(p: [f64; 3]) -> f64 {
+    1.0 + 2.0 * p[0] - p[1] + 0.5 * p[2]
+}
+
+// Unit square split into two triangles.
+fn square() -> Mesh {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(1.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Triangle3(1, 2, 3)));
+    es.insert(2, (1, 1, Topology::Triangle3(1, 3, 4)));
+    Mesh::new(None, ns, es)
+}
+
+#[test]
+fn data() {
+    let mut data = Data::new("temperature", 1).with_time(3, 0.5);
+    assert_eq!(data.insert(1, vec![2.0]), Ok(None));
+    assert_eq!(data.insert(1, vec![4.0]), Ok(Some(vec![2.0])));
+    assert_eq!(data.insert(2, vec![1.0, 2.0]), Err(vec![1.0, 2.0]));
+    assert_eq!(data.name(), "temperature");
+    assert_eq!((data.step(), data.time()), (3, 0.5));
+    assert_eq!(data.get(1), Some(&[4.0][..]));
+    assert_eq!(data.get(2), None);
+}
+
+#[test]
+fn interpolate() {
+    let source = square();
+    let mut data = Data::new("u", 2);
+    for (id, node) in source.nodes() {
+        let p = node.coordinates();
+        data.insert(*id, vec![linear(p), 1.0]).unwrap();
+    }
+
+    // Nodes inside, on the corner of and outside the square.
+    let mut ns = mesh::Nodes::new();
+    ns.insert(10, Node::new(0.25, 0.5, 0.0));
+    ns.insert(11, Node::new(0.75, 0.25, 0.0));
+    ns.insert(12, Node::new(1.0, 1.0, 0.0));
+    ns.insert(13, Node::new(2.0, 0.5, 0.0));
+    let target = Mesh::new(None, ns, mesh::Elements::new());
+
+    let interpolated = source.interpolate(&data, &target);
+    assert_eq!(interpolated.name(), "u");
+    assert_eq!(interpolated.values().len(), 4);
+    for (id, p) in [
+        (10, [0.25, 0.5, 0.0]),
+        (11, [0.75, 0.25, 0.0]),
+        (12, [1.0, 1.0, 0.0]),
+    ] {
+        let value = interpolated.get(id).unwrap();
+        assert!((value[0] - linear(p)).abs() < 1e-12);
+        assert!((value[1] - 1.0).abs() < 1e-12);
+    }
+    // Outside, the value at the closest point of the square.
+    let value = interpolated.get(13).unwrap();
+    assert!((value[0] - linear([1.0, 0.5, 0.0])).abs() < 1e-12);
+
+    data.values.remove(&3);
+    let interpolated = source.interpolate(&data, &target);
+    assert_eq!(interpolated.get(12), None);
+}
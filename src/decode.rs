@@ -6,9 +6,16 @@ use {
 #[cfg(test)]
 mod test;
 
+pub(crate) mod abaqus;
+pub(crate) mod medit;
+pub(crate) mod stl;
+pub(crate) mod su2;
+pub(crate) mod tetgen;
 pub(crate) mod v1;
 pub(crate) mod v2;
 pub(crate) mod v4;
+pub(crate) mod vtk;
+pub(crate) mod vtu;
 
 pub fn mesh<'a, E: ParseError<&'a str>>(i: &'a str) -> IResult<&'a str, Mesh, E> {
     alt((v1::mesh, v2::mesh, map(v4::mesh, v4::Mesh::to_legacy)))(i)
@@ -0,0 +1,235 @@
+//! Abaqus input files, `.inp`.
+
+use {
+    crate::{
+        element::{self, Family, Physical, Topology},
+        encode::abaqus::labels,
+        mesh::{self, Mesh},
+        node::{self, Node},
+    },
+    std::{collections::HashMap, io::Read},
+};
+
+fn error(message: &str) -> std::io::Error {
+    std::io::Error::other(format!("failed to decode Abaqus mesh: {message}"))
+}
+
+/// Family of the Abaqus element type `kind`, ignoring the suffixes for
+/// reduced integration and the like.
+fn family(kind: &str) -> Option<Family> {
+    let base = kind.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let family = match base {
+        "T2D2" | "T3D2" | "B21" | "B31" => Family::Line,
+        "S3" | "CPS3" | "CPE3" | "CAX3" | "M3D3" | "R3D3" | "STRI3" => Family::Triangle,
+        "S4" | "CPS4" | "CPE4" | "CAX4" | "M3D4" | "R3D4" => Family::Quadrangle,
+        "C3D4" => Family::Tetrahedron,
+        "C3D8" => Family::Hexahedron,
+        "C3D6" => Family::Prism,
+        _ => return None,
+    };
+    Some(family)
+}
+
+/// Keyword line: the keyword in upper case and its parameters, with upper
+/// case names.
+fn keyword(line: &str) -> (String, HashMap<String, String>) {
+    let mut fields = line.trim_start_matches('*').split(',');
+    let name = fields
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_uppercase();
+    let parameters = fields
+        .map(|field| {
+            let (key, value) = field.split_once('=').unwrap_or((field, ""));
+            (key.trim().to_ascii_uppercase(), value.trim().to_string())
+        })
+        .collect();
+    (name, parameters)
+}
+
+fn numbers(line: &str) -> Result<Vec<f64>, std::io::Error> {
+    line.split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(|field| field.parse().map_err(|_| error("invalid number")))
+        .collect()
+}
+
+/// Element set named `name`, created on first use.
+fn set<'a>(
+    names: &mut Vec<String>,
+    sets: &'a mut HashMap<String, Vec<element::Id>>,
+    name: &str,
+) -> &'a mut Vec<element::Id> {
+    if !sets.contains_key(name) {
+        names.push(name.to_string());
+    }
+    sets.entry(name.to_string()).or_default()
+}
+
+/// Members of the element set named `name`, which Abaqus compares ignoring
+/// case; none if there is no such set.
+fn members(sets: &HashMap<String, Vec<element::Id>>, name: &str) -> Vec<element::Id> {
+    sets.iter()
+        .find(|(set, _)| set.eq_ignore_ascii_case(name))
+        .map(|(_, ids)| ids.clone())
+        .unwrap_or_default()
+}
+
+/// Face `S<label>` of `topology`, as a surface element.
+fn face(topology: &Topology, label: usize) -> Option<Topology> {
+    let labels = labels(topology.family());
+    let index = labels.iter().position(|&l| l == label)?;
+    let nodes = &topology.faces()[index];
+    let family = match nodes.len() {
+        3 => Family::Triangle,
+        _ => Family::Quadrangle,
+    };
+    Topology::new(family, nodes)
+}
+
+impl Mesh {
+    /// Reads the nodes, elements and element sets of a flat Abaqus input
+    /// file, ignoring parts and instances.
+    ///
+    /// Element sets become physical groups numbered from 1 in order of
+    /// appearance, named after the set, with the same elementary tags; an
+    /// element takes the first set it belongs to, or 0. Element-based
+    /// surfaces on the faces of volume elements become groups of surface
+    /// elements numbered after the existing elements.
+    ///
+    /// Elements of unsupported types, such as second order ones, are skipped,
+    /// as are surface faces that name none of the read elements.
+    pub fn decode_abaqus<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let mut ss = String::new();
+        reader.read_to_string(&mut ss)?;
+
+        let mut nodes = mesh::Nodes::new();
+        let mut topologies: Vec<(element::Id, Topology)> = Vec::new();
+        let mut names: Vec<String> = Vec::new();
+        let mut sets: HashMap<String, Vec<element::Id>> = HashMap::new();
+        let mut surfaces: Vec<(String, element::Id, usize)> = Vec::new();
+
+        let mut section = (String::new(), HashMap::new());
+        let mut pending: Vec<f64> = Vec::new();
+        for line in ss.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with("**") {
+                continue;
+            }
+            if line.starts_with('*') {
+                section = keyword(line);
+                pending.clear();
+                continue;
+            }
+            let (name, parameters) = &section;
+            match name.as_str() {
+                "NODE" => {
+                    let values = numbers(line)?;
+                    let &[id, x, y, ref rest @ ..] = &values[..] else {
+                        return Err(error("invalid node"));
+                    };
+                    let z = rest.first().copied().unwrap_or(0.0);
+                    nodes.insert(id as node::Id, Node::new(x, y, z));
+                }
+                "ELEMENT" => {
+                    let kind = parameters.get("TYPE").map(String::as_str).unwrap_or("");
+                    let Some(family) = family(&kind.to_ascii_uppercase()) else {
+                        continue;
+                    };
+                    // Long elements continue on the following lines.
+                    pending.extend(numbers(line)?);
+                    if pending.len() < family.vertices() + 1 {
+                        continue;
+                    }
+                    let ids: Vec<i32> = pending.drain(..).map(|v| v as i32).collect();
+                    let topology = Topology::new(family, &ids[1..])
+                        .ok_or_else(|| error("wrong number of element nodes"))?;
+                    topologies.push((ids[0], topology));
+                    if let Some(elset) = parameters.get("ELSET") {
+                        set(&mut names, &mut sets, elset).push(ids[0]);
+                    }
+                }
+                "ELSET" => {
+                    let Some(elset) = parameters.get("ELSET") else {
+                        return Err(error("unnamed element set"));
+                    };
+                    if parameters.contains_key("GENERATE") {
+                        let values = numbers(line)?;
+                        let &[first, last, ref step @ ..] = &values[..] else {
+                            return Err(error("invalid generated set"));
+                        };
+                        let step = step.first().copied().unwrap_or(1.0).max(1.0) as usize;
+                        let ids = set(&mut names, &mut sets, elset);
+                        ids.extend((first as i32..=last as i32).step_by(step));
+                    } else {
+                        // Sets may also list the elements of earlier sets by name.
+                        let mut ids = Vec::new();
+                        for field in line.split(',').map(str::trim) {
+                            match field.parse::<f64>() {
+                                Ok(id) => ids.push(id as element::Id),
+                                Err(_) if !field.is_empty() => ids.extend(members(&sets, field)),
+                                Err(_) => {}
+                            }
+                        }
+                        set(&mut names, &mut sets, elset).extend(ids);
+                    }
+                }
+                "SURFACE" => {
+                    let kind = parameters.get("TYPE").map(String::as_str);
+                    if kind.is_some_and(|kind| !kind.eq_ignore_ascii_case("ELEMENT")) {
+                        continue;
+                    }
+                    let Some(surface) = parameters.get("NAME") else {
+                        return Err(error("unnamed surface"));
+                    };
+                    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+                    let &[owner, label, ..] = &fields[..] else {
+                        return Err(error("invalid surface"));
+                    };
+                    set(&mut names, &mut sets, surface);
+                    // Labels other than S<n>, such as SPOS of shells, are skipped.
+                    let label = label.to_ascii_uppercase();
+                    let Some(label) = label.strip_prefix('S').and_then(|l| l.parse().ok()) else {
+                        continue;
+                    };
+                    let owners = match owner.parse::<f64>() {
+                        Ok(id) => vec![id as element::Id],
+                        Err(_) => members(&sets, owner),
+                    };
+                    surfaces.extend(owners.into_iter().map(|id| (surface.clone(), id, label)));
+                }
+                _ => {}
+            }
+        }
+
+        let mut tags: HashMap<element::Id, Physical> = HashMap::new();
+        for (index, name) in names.iter().enumerate() {
+            for id in &sets[name] {
+                tags.entry(*id).or_insert(index as Physical + 1);
+            }
+        }
+        let mut elements = mesh::Elements::new();
+        let mut physical_names = mesh::PhysicalNames::new();
+        let owners: HashMap<element::Id, Topology> = topologies.iter().copied().collect();
+        let mut next = topologies.iter().map(|&(id, _)| id).max().unwrap_or(0);
+        for (surface, owner, label) in surfaces {
+            let Some(topology) = owners.get(&owner).and_then(|t| face(t, label)) else {
+                continue;
+            };
+            let physical = names.iter().position(|n| *n == surface).unwrap_or(0) as Physical + 1;
+            physical_names.insert((2, physical), surface);
+            next += 1;
+            elements.insert(next, (physical, physical, topology));
+        }
+        for (id, topology) in topologies {
+            let physical = tags.get(&id).copied().unwrap_or(0);
+            if physical != 0 {
+                let name = names[physical as usize - 1].clone();
+                physical_names.insert((topology.dimension(), physical), name);
+            }
+            elements.insert(id, (physical, physical, topology));
+        }
+        Ok(Mesh::new(None, nodes, elements).with_physical_names(physical_names))
+    }
+}
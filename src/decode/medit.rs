@@ -0,0 +1,278 @@
+//! INRIA MEDIT meshes, ASCII `.mesh` and binary `.meshb`.
+
+use {
+    crate::{
+        element::{Family, Topology},
+        encode::medit::{DIMENSION, END, KEYWORDS, VERTICES},
+        mesh::{self, Mesh},
+        node::Node,
+    },
+    std::io::Read,
+};
+
+fn error(message: &str) -> std::io::Error {
+    std::io::Error::other(format!("failed to decode MEDIT mesh: {message}"))
+}
+
+/// Vertices and elements read from either kind of file, with references.
+#[derive(Default)]
+struct Sections {
+    points: Vec<[f64; 3]>,
+    elements: Vec<(Family, Vec<i64>, i64)>,
+}
+
+impl Sections {
+    fn mesh(self) -> Result<Mesh, std::io::Error> {
+        // One-based id of the zero-based `index`.
+        let id = |index: usize| index.checked_add(1).and_then(|i| i32::try_from(i).ok());
+        let mut nodes = mesh::Nodes::new();
+        for (index, p) in self.points.iter().enumerate() {
+            let id = id(index).ok_or_else(|| error("too many vertices"))?;
+            nodes.insert(id, Node::new(p[0], p[1], p[2]));
+        }
+        let mut elements = mesh::Elements::new();
+        for (index, (family, vertices, reference)) in self.elements.into_iter().enumerate() {
+            let ids: Vec<i32> = vertices
+                .iter()
+                .map(|&v| i32::try_from(v).ok().filter(|id| nodes.contains_key(id)))
+                .collect::<Option<_>>()
+                .ok_or_else(|| error("element over a missing vertex"))?;
+            let topology = Topology::new(family, &ids).ok_or_else(|| error("invalid element"))?;
+            let reference = i32::try_from(reference).map_err(|_| error("invalid reference"))?;
+            let id = id(index).ok_or_else(|| error("too many elements"))?;
+            elements.insert(id, (reference, reference, topology));
+        }
+        Ok(Mesh::new(None, nodes, elements))
+    }
+}
+
+/// Sections without elements that the ASCII reader skips, such as those of
+/// mmg, with the number of values in each record; 0 stands for the
+/// dimension. Other unknown keywords are errors.
+const SKIPPED: [(&str, usize); 12] = [
+    ("Corners", 1),
+    ("Ridges", 1),
+    ("RequiredVertices", 1),
+    ("RequiredEdges", 1),
+    ("RequiredTriangles", 1),
+    ("RequiredQuadrilaterals", 1),
+    ("RequiredTetrahedra", 1),
+    ("Normals", 0),
+    ("Tangents", 0),
+    ("NormalAtVertices", 2),
+    ("TangentAtVertices", 2),
+    ("NormalAtTriangleVertices", 3),
+];
+
+fn number<'a, I: Iterator<Item = &'a str>>(tokens: &mut I) -> Result<f64, std::io::Error> {
+    let token = tokens
+        .next()
+        .ok_or_else(|| error("unexpected end of file"))?;
+    token.parse().map_err(|_| error("invalid number"))
+}
+
+fn ascii(ss: &str) -> Result<Sections, std::io::Error> {
+    // Comments run to the end of their line.
+    let body: Vec<&str> = ss
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .collect();
+    let body = body.join("\n");
+    let mut tokens = body.split_whitespace();
+
+    let mut sections = Sections::default();
+    let mut dimension = 3;
+    while let Some(keyword) = tokens.next() {
+        match keyword {
+            "End" => break,
+            "MeshVersionFormatted" => {
+                number(&mut tokens)?;
+            }
+            "Dimension" => dimension = number(&mut tokens)? as usize,
+            "Vertices" => {
+                let n = number(&mut tokens)? as usize;
+                for _ in 0..n {
+                    let mut p = [0.0; 3];
+                    for x in p.iter_mut().take(dimension) {
+                        *x = number(&mut tokens)?;
+                    }
+                    number(&mut tokens)?;
+                    sections.points.push(p);
+                }
+            }
+            _ => {
+                if let Some(&(_, width)) = SKIPPED.iter().find(|(k, _)| *k == keyword) {
+                    let width = match width {
+                        0 => dimension,
+                        width => width,
+                    };
+                    let n = number(&mut tokens)? as usize;
+                    let size = n.checked_mul(width).ok_or_else(|| error("invalid count"))?;
+                    for _ in 0..size {
+                        number(&mut tokens)?;
+                    }
+                    continue;
+                }
+                let Some(&(family, ..)) = KEYWORDS.iter().find(|(_, k, _)| *k == keyword) else {
+                    return Err(error("unsupported keyword"));
+                };
+                let n = number(&mut tokens)? as usize;
+                for _ in 0..n {
+                    let vertices = (0..family.vertices())
+                        .map(|_| number(&mut tokens).map(|v| v as i64))
+                        .collect::<Result<_, _>>()?;
+                    let reference = number(&mut tokens)? as i64;
+                    sections.elements.push((family, vertices, reference));
+                }
+            }
+        }
+    }
+    Ok(sections)
+}
+
+/// Cursor over a binary file of the given version and byte order.
+struct Binary<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    version: i64,
+    big: bool,
+}
+
+impl Binary<'_> {
+    fn take(&mut self, n: usize) -> Result<Vec<u8>, std::io::Error> {
+        let end = self
+            .position
+            .checked_add(n)
+            .ok_or_else(|| error("unexpected end of file"))?;
+        let mut bytes = self
+            .bytes
+            .get(self.position..end)
+            .ok_or_else(|| error("unexpected end of file"))?
+            .to_vec();
+        self.position = end;
+        if !self.big {
+            bytes.reverse();
+        }
+        Ok(bytes)
+    }
+
+    fn i32(&mut self) -> Result<i64, std::io::Error> {
+        let b = self.take(4)?;
+        Ok(i32::from_be_bytes([b[0], b[1], b[2], b[3]]) as i64)
+    }
+
+    fn i64(&mut self) -> Result<i64, std::io::Error> {
+        let b = self.take(8)?;
+        Ok(i64::from_be_bytes([
+            b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7],
+        ]))
+    }
+
+    /// Integer data, 64 bit from version 4.
+    fn int(&mut self) -> Result<i64, std::io::Error> {
+        match self.version >= 4 {
+            true => self.i64(),
+            false => self.i32(),
+        }
+    }
+
+    /// Keyword positions, 64 bit from version 3.
+    fn position(&mut self) -> Result<i64, std::io::Error> {
+        match self.version >= 3 {
+            true => self.i64(),
+            false => self.i32(),
+        }
+    }
+
+    /// Real data, single precision in version 1.
+    fn real(&mut self) -> Result<f64, std::io::Error> {
+        if self.version == 1 {
+            let b = self.take(4)?;
+            Ok(f32::from_be_bytes([b[0], b[1], b[2], b[3]]) as f64)
+        } else {
+            let b = self.take(8)?;
+            Ok(f64::from_be_bytes([
+                b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7],
+            ]))
+        }
+    }
+}
+
+fn binary(bytes: &[u8]) -> Result<Sections, std::io::Error> {
+    let big = bytes.get(..4) == Some(&[0, 0, 0, 1]);
+    let mut file = Binary {
+        bytes,
+        position: 4,
+        version: 1,
+        big,
+    };
+    file.version = file.i32()?;
+    if !(1..=4).contains(&file.version) {
+        return Err(error("unsupported version"));
+    }
+
+    let mut sections = Sections::default();
+    let mut dimension = 3;
+    while file.position < bytes.len() {
+        let start = file.position;
+        let code = file.i32()? as i32;
+        let next = usize::try_from(file.position()?).map_err(|_| error("invalid position"))?;
+        if code == END {
+            break;
+        }
+        match code {
+            DIMENSION => dimension = file.int()? as usize,
+            VERTICES => {
+                let n = file.int()?;
+                for _ in 0..n {
+                    let mut p = [0.0; 3];
+                    for x in p.iter_mut().take(dimension) {
+                        *x = file.real()?;
+                    }
+                    file.int()?;
+                    sections.points.push(p);
+                }
+            }
+            _ => {
+                if let Some(&(family, ..)) = KEYWORDS.iter().find(|(.., c)| *c == code) {
+                    let n = file.int()?;
+                    for _ in 0..n {
+                        let vertices = (0..family.vertices())
+                            .map(|_| file.int())
+                            .collect::<Result<_, _>>()?;
+                        let reference = file.int()?;
+                        sections.elements.push((family, vertices, reference));
+                    }
+                }
+                if next == 0 {
+                    break;
+                }
+                // Positions only move forward, or the file would loop.
+                if next <= start {
+                    return Err(error("invalid position"));
+                }
+                file.position = next;
+            }
+        }
+    }
+    Ok(sections)
+}
+
+impl Mesh {
+    /// Reads an ASCII or binary MEDIT file; vertices and elements are
+    /// numbered from 1 in file order, with element references as physical
+    /// and elementary tags. Vertex references are dropped, as are the
+    /// corners, ridges, required entities and normals of mmg.
+    pub fn decode_medit<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let sections = match bytes.get(..4) {
+            Some([1, 0, 0, 0] | [0, 0, 0, 1]) => binary(&bytes)?,
+            _ => {
+                let ss = std::str::from_utf8(&bytes).map_err(|_| error("invalid text"))?;
+                ascii(ss)?
+            }
+        };
+        sections.mesh()
+    }
+}
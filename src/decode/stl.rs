@@ -0,0 +1,118 @@
+//! STL triangulated surfaces, in ASCII or binary.
+
+use {
+    crate::{
+        element::{Physical, Topology},
+        geometry::Point,
+        merge::Welder,
+        mesh::{self, Mesh},
+        node::Node,
+    },
+    std::io::Read,
+};
+
+fn error(message: &str) -> std::io::Error {
+    std::io::Error::other(format!("failed to decode STL mesh: {message}"))
+}
+
+/// Triangles of a binary file, with their attributes as physical tags.
+fn binary(bytes: &[u8]) -> Vec<(Physical, [Point; 3])> {
+    bytes[84..]
+        .chunks_exact(50)
+        .map(|facet| {
+            let value = |i: usize| {
+                let b = &facet[4 * i..4 * i + 4];
+                f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64
+            };
+            let point = |v: usize| [0, 1, 2].map(|a| value(3 + 3 * v + a));
+            let attribute = u16::from_le_bytes([facet[48], facet[49]]);
+            (attribute as Physical, [point(0), point(1), point(2)])
+        })
+        .collect()
+}
+
+/// Triangles of an ASCII file, tagged with the position of their solid from
+/// 1, and the names of the solids.
+type Solids = (Vec<(Physical, [Point; 3])>, Vec<String>);
+
+fn ascii(ss: &str) -> Result<Solids, std::io::Error> {
+    let mut triangles = Vec::new();
+    let mut names = Vec::new();
+    let mut vertices = Vec::new();
+    for line in ss.lines() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("solid") => names.push(words.collect::<Vec<_>>().join(" ")),
+            Some("vertex") => {
+                let coordinates: Vec<f64> = words
+                    .map(|w| w.parse().map_err(|_| error("invalid vertex")))
+                    .collect::<Result<_, _>>()?;
+                match coordinates[..] {
+                    [x, y, z] => vertices.push([x, y, z]),
+                    _ => return Err(error("invalid vertex")),
+                }
+            }
+            Some("endloop") => {
+                let [a, b, c] = vertices[..] else {
+                    return Err(error("facets must be triangles"));
+                };
+                triangles.push((names.len() as Physical, [a, b, c]));
+                vertices.clear();
+            }
+            _ => {}
+        }
+    }
+    if names.is_empty() {
+        return Err(error("missing solid"));
+    }
+    Ok((triangles, names))
+}
+
+impl Mesh {
+    /// Reads an ASCII or binary STL file into triangle elements, welding
+    /// vertices closer than `tolerance` into shared nodes.
+    ///
+    /// Triangles of ASCII files take the position of their solid, from 1, as
+    /// physical and elementary tags, and the solid names as physical names;
+    /// those of binary files take their attribute.
+    pub fn decode_stl<R: Read>(reader: &mut R, tolerance: f64) -> Result<Self, std::io::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        // Binary files may start with "solid" too, their size tells them apart.
+        let count = bytes
+            .get(80..84)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize);
+        let (triangles, names) = match count {
+            Some(count) if bytes.len() == 84 + 50 * count => (binary(&bytes), vec![]),
+            _ => {
+                let ss = std::str::from_utf8(&bytes).map_err(|_| error("invalid text"))?;
+                ascii(ss)?
+            }
+        };
+
+        let mut welder = Welder::new(tolerance);
+        let mut nodes = mesh::Nodes::new();
+        let mut elements = mesh::Elements::new();
+        for (physical, points) in triangles {
+            let ids = points.map(|p| {
+                let id = nodes.len() as i32 + 1;
+                let welded = welder.weld(id, p);
+                if welded == id {
+                    nodes.insert(id, Node::new(p[0], p[1], p[2]));
+                }
+                welded
+            });
+            let topology = Topology::Triangle3(ids[0], ids[1], ids[2]);
+            elements.insert(elements.len() as i32 + 1, (physical, physical, topology));
+        }
+
+        let names = names
+            .into_iter()
+            .enumerate()
+            .filter(|(_, name)| !name.is_empty())
+            .map(|(index, name)| ((2, index as Physical + 1), name))
+            .collect();
+        Ok(Mesh::new(None, nodes, elements).with_physical_names(names))
+    }
+}
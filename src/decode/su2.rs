@@ -0,0 +1,132 @@
+//! SU2 native meshes, `.su2`.
+
+use {
+    crate::{
+        decode::vtk,
+        element::{Physical, Topology},
+        mesh::{self, Mesh},
+        node::Node,
+    },
+    std::io::Read,
+};
+
+fn error(message: &str) -> std::io::Error {
+    std::io::Error::other(format!("failed to decode SU2 mesh: {message}"))
+}
+
+/// Value of a `KEY= value` line.
+fn value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let (k, v) = line.split_once('=')?;
+    (k.trim() == key).then(|| v.trim())
+}
+
+fn count(line: Option<&str>, key: &str) -> Result<usize, std::io::Error> {
+    line.and_then(|line| value(line, key))
+        .and_then(|v| v.split_whitespace().next())
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| error(&format!("missing {key}")))
+}
+
+/// One-based id of the zero-based `index`.
+fn id(index: usize) -> Option<i32> {
+    index.checked_add(1).and_then(|i| i32::try_from(i).ok())
+}
+
+/// Element of a row of point indices, numbering points from 1.
+fn topology(line: &str) -> Result<Topology, std::io::Error> {
+    let values: Vec<usize> = line
+        .split_whitespace()
+        .map(|v| v.parse().map_err(|_| error("invalid element")))
+        .collect::<Result<_, _>>()?;
+    let (&cell, nodes) = values.split_first().ok_or_else(|| error("empty element"))?;
+    let family = u8::try_from(cell)
+        .ok()
+        .and_then(vtk::family)
+        .ok_or_else(|| error("unsupported element type"))?;
+    let nodes: Vec<i32> = nodes
+        .iter()
+        .take(family.vertices())
+        .map(|&i| id(i))
+        .collect::<Option<_>>()
+        .ok_or_else(|| error("invalid element"))?;
+    Topology::new(family, &nodes).ok_or_else(|| error("wrong number of element nodes"))
+}
+
+impl Mesh {
+    /// Reads a single zone ASCII SU2 file.
+    ///
+    /// Points and elements are numbered from 1, volume elements first with
+    /// tags 0, then the elements of each marker, tagged with the position of
+    /// the marker from 1 and named after it.
+    pub fn decode_su2<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let mut ss = String::new();
+        reader.read_to_string(&mut ss)?;
+        let mut lines = ss
+            .lines()
+            .map(|line| line.split('%').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty());
+        let mut row = || lines.next().ok_or_else(|| error("unexpected end of file"));
+
+        let mut nodes = mesh::Nodes::new();
+        let mut elements = mesh::Elements::new();
+        let mut names = mesh::PhysicalNames::new();
+        let mut dimension = 3;
+        while let Ok(line) = row() {
+            let Some((key, _)) = line.split_once('=') else {
+                continue;
+            };
+            match key.trim() {
+                "NDIME" => {
+                    dimension = count(Some(line), "NDIME")?;
+                    if !(2..=3).contains(&dimension) {
+                        return Err(error("invalid dimension"));
+                    }
+                }
+                "NELEM" => {
+                    for _ in 0..count(Some(line), "NELEM")? {
+                        let topology = topology(row()?)?;
+                        let id = id(elements.len()).ok_or_else(|| error("too many elements"))?;
+                        elements.insert(id, (0, 0, topology));
+                    }
+                }
+                "NPOIN" => {
+                    for index in 0..count(Some(line), "NPOIN")? {
+                        let row = row()?;
+                        let values: Vec<f64> = row
+                            .split_whitespace()
+                            .take(dimension)
+                            .map(|v| v.parse().map_err(|_| error("invalid point")))
+                            .collect::<Result<_, _>>()?;
+                        let mut p = [0.0; 3];
+                        p[..values.len()].copy_from_slice(&values);
+                        let id = id(index).ok_or_else(|| error("too many points"))?;
+                        nodes.insert(id, Node::new(p[0], p[1], p[2]));
+                    }
+                }
+                "NMARK" => {
+                    for marker in 0..count(Some(line), "NMARK")? {
+                        let physical: Physical =
+                            id(marker).ok_or_else(|| error("too many markers"))?;
+                        let name = value(row()?, "MARKER_TAG")
+                            .ok_or_else(|| error("missing MARKER_TAG"))?;
+                        for _ in 0..count(row().ok(), "MARKER_ELEMS")? {
+                            let topology = topology(row()?)?;
+                            names.insert((topology.dimension(), physical), name.to_string());
+                            let id =
+                                id(elements.len()).ok_or_else(|| error("too many elements"))?;
+                            elements.insert(id, (physical, physical, topology));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        // Elements may come before the points they refer to.
+        let missing =
+            |topology: &Topology| topology.nodes().iter().any(|id| !nodes.contains_key(id));
+        if elements.values().any(|(.., topology)| missing(topology)) {
+            return Err(error("element over a missing node"));
+        }
+        Ok(Mesh::new(None, nodes, elements).with_physical_names(names))
+    }
+}
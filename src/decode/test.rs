@@ -1,6 +1,10 @@
+#![allow(clippy::approx_constant, clippy::assertions_on_constants)]
+
 use {
     crate::{
+        base64,
         element::Topology,
+        encode::{vtu::Encoding, Surface},
         format::Format,
         mesh::{self, Mesh},
         node::Node,
@@ -428,10 +432,589 @@ $Elements
 31 10 11 12 
 $EndElements
 ";
+    let mut ns = mesh::Nodes::new();
+
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(0.0, 1.0, 0.0));
+    ns.insert(3, Node::new(0.7071067811865475, 0.7071067811865476, 0.0));
+    ns.insert(4, Node::new(1.0, 0.0, 0.0));
+    ns.insert(5, Node::new(0.7071067811865476, -0.7071067811865475, 0.0));
+    ns.insert(6, Node::new(0.0, -1.0, 0.0));
+    ns.insert(7, Node::new(-0.7071067811865475, -0.7071067811865477, 0.0));
+    ns.insert(8, Node::new(-1.0, 0.0, 0.0));
+    ns.insert(9, Node::new(-0.7071067811865477, 0.7071067811865474, 0.0));
+    ns.insert(10, Node::new(-0.1113264466680611, -0.2687658173968382, 0.0));
+    ns.insert(11, Node::new(0.150474547846242, 0.3632776942023567, 0.0));
+    ns.insert(12, Node::new(0.4510798725986296, -0.1820395192602642, 0.0));
+    ns.insert(13, Node::new(-0.447683015284127, 0.1902402582583829, -0.0));
+
+    let mut es = mesh::Elements::new();
+
+    es.insert(1, (0, 1, Topology::Point1(1)));
+    es.insert(2, (0, 2, Topology::Point1(2)));
+    es.insert(3, (0, 3, Topology::Point1(3)));
+    es.insert(4, (0, 4, Topology::Point1(4)));
+    es.insert(5, (0, 5, Topology::Point1(5)));
+    es.insert(6, (0, 6, Topology::Point1(6)));
+    es.insert(7, (0, 7, Topology::Point1(7)));
+    es.insert(8, (0, 8, Topology::Point1(8)));
+    es.insert(9, (0, 9, Topology::Point1(9)));
+
+    es.insert(10, (0, 1, Topology::Line2(2, 3)));
+    es.insert(11, (0, 2, Topology::Line2(3, 4)));
+    es.insert(12, (0, 3, Topology::Line2(4, 5)));
+    es.insert(13, (0, 4, Topology::Line2(5, 6)));
+    es.insert(14, (0, 5, Topology::Line2(6, 7)));
+    es.insert(15, (0, 6, Topology::Line2(7, 8)));
+    es.insert(16, (0, 7, Topology::Line2(8, 9)));
+    es.insert(17, (0, 8, Topology::Line2(9, 2)));
+
+    es.insert(18, (0, 6, Topology::Triangle3(9, 2, 13)));
+    es.insert(19, (0, 6, Topology::Triangle3(3, 4, 12)));
+    es.insert(20, (0, 6, Topology::Triangle3(5, 6, 12)));
+    es.insert(21, (0, 6, Topology::Triangle3(6, 10, 12)));
+    es.insert(22, (0, 6, Topology::Triangle3(7, 8, 13)));
+    es.insert(23, (0, 6, Topology::Triangle3(10, 7, 13)));
+    es.insert(24, (0, 6, Topology::Triangle3(2, 11, 13)));
+    es.insert(25, (0, 6, Topology::Triangle3(11, 3, 12)));
+    es.insert(26, (0, 6, Topology::Triangle3(6, 7, 10)));
+    es.insert(27, (0, 6, Topology::Triangle3(2, 3, 11)));
+    es.insert(28, (0, 6, Topology::Triangle3(4, 5, 12)));
+    es.insert(29, (0, 6, Topology::Triangle3(8, 9, 13)));
+    es.insert(30, (0, 6, Topology::Triangle3(11, 10, 13)));
+    es.insert(31, (0, 6, Topology::Triangle3(10, 11, 12)));
+
+    let f = Format::new(4.1, 0, 8);
+    let expected = Mesh::new(Some(f), ns, es);
+
     super::v4::mesh::<(&str, ErrorKind)>(text).unwrap();
 
-    super::mesh::<(&str, ErrorKind)>(text).unwrap();
+    let (_, actual) = super::mesh::<(&str, ErrorKind)>(text).unwrap();
+    assert_eq!(actual, expected);
 
     let mut cursor = Cursor::new(text);
-    Mesh::decode(&mut cursor).unwrap();
+    let actual = Mesh::decode(&mut cursor).unwrap();
+    assert_eq!(actual, expected);
+}
+
+// Unit square split into two triangles, with an edge on its boundary and
+// sparse ids.
+fn square() -> Mesh {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(1.0, 1.0, 0.5));
+    ns.insert(5, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 10, Topology::Triangle3(1, 2, 3)));
+    es.insert(2, (1, 10, Topology::Triangle3(1, 3, 5)));
+    es.insert(7, (2, 20, Topology::Line2(1, 2)));
+    Mesh::new(None, ns, es)
+}
+
+#[test]
+fn vtk() {
+    let mesh = square();
+    let expected = mesh.compact(1).into_mesh();
+
+    let mut bytes = Vec::new();
+    mesh.encode_vtk(&mut bytes, &[], &[]).unwrap();
+    let decoded = Mesh::decode_vtk(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(decoded, expected);
+
+    // VTK 5.1 cell layout, with tags as scalars.
+    let ss = "\
+# vtk DataFile Version 5.1
+written elsewhere
+ASCII
+DATASET UNSTRUCTURED_GRID
+POINTS 4 float
+0 0 0 1 0 0
+1 1 0.5 0 1 0
+CELLS 4 8
+OFFSETS vtktypeint64
+0 3 6 8
+CONNECTIVITY vtktypeint64
+0 1 2 0 2 3 0 1
+CELL_TYPES 3
+5 5 3
+CELL_DATA 3
+SCALARS gmsh:physical int 1
+LOOKUP_TABLE default
+1 1 2
+VECTORS normals double
+0 0 1 0 0 1 0 0 1
+SCALARS gmsh:geometrical int
+LOOKUP_TABLE default
+10 10 20
+";
+    let decoded = Mesh::decode_vtk(&mut Cursor::new(ss)).unwrap();
+    assert_eq!(decoded, expected);
+    // 261 would wrap around to a triangle.
+    let wrapped = ss.replace("5 5 3", "261 5 3");
+    assert!(Mesh::decode_vtk(&mut Cursor::new(wrapped)).is_err());
+    let wrapped = ss.replace("0 2 3 0 1", "0 2 3 0 4294967296");
+    assert!(Mesh::decode_vtk(&mut Cursor::new(wrapped)).is_err());
+    let huge = ss.replace("POINTS 4", "POINTS 9223372036854775807");
+    assert!(Mesh::decode_vtk(&mut Cursor::new(huge)).is_err());
+
+    let binary = "# vtk DataFile Version 2.0\nrutile\nBINARY\n";
+    assert!(Mesh::decode_vtk(&mut Cursor::new(binary)).is_err());
+}
+
+#[test]
+fn vtu() {
+    let mesh = square();
+    let expected = mesh.compact(1).into_mesh();
+
+    for encoding in [Encoding::Ascii, Encoding::Base64, Encoding::Raw] {
+        let mut bytes = Vec::new();
+        mesh.encode_vtu(&mut bytes, &[], &[], encoding).unwrap();
+        let decoded = Mesh::decode_vtu(&mut Cursor::new(bytes)).unwrap();
+        assert_eq!(decoded, expected, "{encoding:?}");
+    }
+
+    // Base64 appended data with 32 bit headers encoded apart from the data,
+    // and no tags.
+    let block = |bytes: &[u8]| {
+        let header = (bytes.len() as u32).to_le_bytes();
+        base64::encode(&header) + &base64::encode(bytes)
+    };
+    let points: Vec<u8> = [0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    let connectivity: Vec<u8> = [0i32, 1, 2].iter().flat_map(|v| v.to_le_bytes()).collect();
+    let offsets = 3i32.to_le_bytes();
+    let types = [5u8];
+    let arrays = [
+        block(&points),
+        block(&connectivity),
+        block(&offsets),
+        block(&types),
+    ];
+    let offset = |n: usize| arrays[..n].iter().map(String::len).sum::<usize>();
+    let ss = format!(
+        r#"<?xml version="1.0"?>
+<VTKFile type="UnstructuredGrid" version="0.1" byte_order="LittleEndian">
+  <UnstructuredGrid>
+    <Piece NumberOfPoints="3" NumberOfCells="1">
+      <Points>
+        <DataArray type="Float32" NumberOfComponents="3" format="appended" offset="0"/>
+      </Points>
+      <Cells>
+        <DataArray type="Int32" Name="connectivity" format="appended" offset="{}"/>
+        <DataArray type="Int32" Name="offsets" format="appended" offset="{}"/>
+        <DataArray type="UInt8" Name="types" format="appended" offset="{}"/>
+      </Cells>
+    </Piece>
+  </UnstructuredGrid>
+  <AppendedData encoding="base64">
+   _{}
+  </AppendedData>
+</VTKFile>
+"#,
+        offset(1),
+        offset(2),
+        offset(3),
+        arrays.concat()
+    );
+    let decoded = Mesh::decode_vtu(&mut Cursor::new(&ss)).unwrap();
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (0, 0, Topology::Triangle3(1, 2, 3)));
+    assert_eq!(decoded, Mesh::new(None, ns, es));
+
+    let capitalized = ss.replace("Name=\"offsets\"", "Name=\"Offsets\"");
+    assert_eq!(
+        Mesh::decode_vtu(&mut Cursor::new(capitalized)).unwrap(),
+        decoded
+    );
+    let quoted = ss.replace("type=\"UInt8\"", "type=«UInt8»");
+    assert!(Mesh::decode_vtu(&mut Cursor::new(quoted)).is_err());
+}
+
+#[test]
+fn base64() {
+    for ss in ["", "f", "fo", "foo", "foob", "fooba", "foobar"] {
+        let encoded = base64::encode(ss.as_bytes());
+        assert_eq!(base64::decode(&encoded), Some(ss.as_bytes().to_vec()));
+    }
+    assert_eq!(base64::decode("Zg==Zm8="), Some(b"ffo".to_vec()));
+    assert_eq!(base64::decode("Zm9"), None);
+    assert_eq!(base64::decode("Zm9*"), None);
+}
+
+#[test]
+fn stl() {
+    let mesh = square();
+
+    let mut bytes = Vec::new();
+    mesh.encode_stl(&mut bytes, Surface::Elements, false)
+        .unwrap();
+    let decoded = Mesh::decode_stl(&mut Cursor::new(bytes), 0.0).unwrap();
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(1.0, 1.0, 0.5));
+    ns.insert(4, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Triangle3(1, 2, 3)));
+    es.insert(2, (1, 1, Topology::Triangle3(1, 3, 4)));
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((2, 1), String::from("rutile"));
+    let expected = Mesh::new(None, ns, es).with_physical_names(names);
+    assert_eq!(decoded, expected);
+
+    // Binary coordinates are single precision; welding absorbs the rounding.
+    let mut bytes = Vec::new();
+    mesh.encode_stl_binary(&mut bytes, Surface::Elements)
+        .unwrap();
+    let decoded = Mesh::decode_stl(&mut Cursor::new(bytes), 1e-6).unwrap();
+    assert_eq!(decoded.nodes().len(), 4);
+    assert_eq!(decoded.elements(), expected.elements());
+
+    // Separate solids, and vertices welded within the tolerance or only when
+    // equal.
+    let ss = "\
+solid a
+facet normal 0 0 1
+outer loop
+vertex 0 0 0
+vertex 1 0 0
+vertex 0 1 0
+endloop
+endfacet
+endsolid a
+solid b
+facet normal 0 0 1
+outer loop
+vertex 1.0000001 0 0
+vertex 1 1 0
+vertex 0 1 0
+endloop
+endfacet
+endsolid b
+";
+    let decoded = Mesh::decode_stl(&mut Cursor::new(ss), 1e-3).unwrap();
+    assert_eq!(decoded.nodes().len(), 4);
+    assert_eq!(decoded.elements()[&2], (2, 2, Topology::Triangle3(2, 4, 3)));
+    assert_eq!(decoded.physical_names()[&(2, 2)], "b");
+    assert_eq!(
+        Mesh::decode_stl(&mut Cursor::new(ss), 0.0)
+            .unwrap()
+            .nodes()
+            .len(),
+        5
+    );
+}
+
+#[test]
+fn abaqus() {
+    let mut bytes = Vec::new();
+    square().encode_abaqus(&mut bytes).unwrap();
+    let decoded = Mesh::decode_abaqus(&mut Cursor::new(bytes)).unwrap();
+
+    // Sets are numbered in file order: the line set comes first.
+    let mut expected = square();
+    for (physical, elementary, _) in expected.elements.values_mut() {
+        *physical = 3 - *physical;
+        *elementary = *physical;
+    }
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((1, 1), String::from("physical_1_2"));
+    names.insert((2, 2), String::from("physical_2_1"));
+    assert_eq!(decoded, expected.with_physical_names(names));
+
+    let ss = "\
+** Written elsewhere
+*Heading
+*Node, nset=all
+1, 0., 0., 0.
+2, 1., 0., 0.
+3, 1., 1., 0.
+4, 0., 1., 0.
+5, 0., 0., 1.
+6, 1., 0., 1.
+7, 1., 1., 1.
+8, 0., 1., 1.
+*Element, type=C3D8R, elset=block
+1, 1, 2, 3, 4, 5, 6,
+7, 8
+*Elset, elset=all, generate
+1, 1, 1
+*Material, name=steel
+*Elastic
+210e9, 0.3
+";
+    let decoded = Mesh::decode_abaqus(&mut Cursor::new(ss)).unwrap();
+    assert_eq!(decoded.nodes().len(), 8);
+    assert_eq!(
+        decoded.elements()[&1],
+        (1, 1, Topology::Hexahedron8(1, 2, 3, 4, 5, 6, 7, 8))
+    );
+    assert_eq!(decoded.physical_names()[&(3, 1)], "block");
+
+    // Second order elements are skipped, sets may name other sets, and
+    // surfaces become surface elements.
+    let ss = "\
+*NODE
+1, 0, 0, 0
+2, 1, 0, 0
+3, 0, 1, 0
+4, 0, 0, 1
+*ELEMENT, TYPE=C3D10, ELSET=quadratic
+5, 1, 2, 3, 4, 1, 1, 1, 1, 1, 1
+*ELEMENT, TYPE=C3D4
+7, 1, 2, 3, 4
+*ELSET, ELSET=tets
+7
+*ELSET, ELSET=all
+Tets, quadratic
+*SURFACE, NAME=side
+all, S3
+7, SPOS
+";
+    let decoded = Mesh::decode_abaqus(&mut Cursor::new(ss)).unwrap();
+    assert_eq!(decoded.elements().len(), 2);
+    assert_eq!(
+        decoded.elements()[&7],
+        (1, 1, Topology::Tetrahedron4(1, 2, 3, 4))
+    );
+    assert_eq!(decoded.elements()[&8], (3, 3, Topology::Triangle3(4, 2, 3)));
+    assert_eq!(decoded.physical_names()[&(2, 3)], "side");
+
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(0.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 0.0, 1.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (2, 2, Topology::Tetrahedron4(1, 2, 3, 4)));
+    es.insert(2, (1, 1, Topology::Triangle3(1, 3, 2)));
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((2, 1), String::from("bottom"));
+    names.insert((3, 2), String::from("solid"));
+    let mesh = Mesh::new(None, ns, es).with_physical_names(names);
+    let mut bytes = Vec::new();
+    mesh.encode_abaqus(&mut bytes).unwrap();
+    let decoded = Mesh::decode_abaqus(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(decoded, mesh);
+}
+
+#[test]
+fn medit() {
+    // Edges come before triangles, and elementary tags follow the physical.
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(1.0, 1.0, 0.5));
+    ns.insert(4, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (2, 2, Topology::Line2(1, 2)));
+    es.insert(2, (1, 1, Topology::Triangle3(1, 2, 3)));
+    es.insert(3, (1, 1, Topology::Triangle3(1, 3, 4)));
+    let expected = Mesh::new(None, ns, es);
+
+    let mut bytes = Vec::new();
+    square().encode_medit(&mut bytes).unwrap();
+    assert_eq!(
+        Mesh::decode_medit(&mut Cursor::new(bytes)).unwrap(),
+        expected
+    );
+
+    let mut bytes = Vec::new();
+    square().encode_medit_binary(&mut bytes).unwrap();
+    assert_eq!(
+        Mesh::decode_medit(&mut Cursor::new(bytes)).unwrap(),
+        expected
+    );
+
+    // An unknown keyword pointing back at itself.
+    let looping: Vec<u8> = [1i32, 2, 99, 8]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    assert!(Mesh::decode_medit(&mut Cursor::new(looping)).is_err());
+
+    let ss = "\
+MeshVersionFormatted 1
+# A planar mesh.
+Dimension 2
+Vertices
+3
+0 0 1
+1 0 1
+0 1 1
+Triangles 1
+1 2 3 7
+End
+";
+    let decoded = Mesh::decode_medit(&mut Cursor::new(ss)).unwrap();
+    assert_eq!(decoded.nodes()[&3], Node::new(0.0, 1.0, 0.0));
+    assert_eq!(decoded.elements()[&1], (7, 7, Topology::Triangle3(1, 2, 3)));
+
+    // Feature sections written by mmg are skipped.
+    let ss = "\
+MeshVersionFormatted 2
+Dimension 3
+Vertices
+3
+0 0 0 1
+1 0 0 1
+0 1 0 1
+Corners
+2
+1 3
+Ridges
+1
+1
+Edges
+1
+1 2 4
+Triangles
+1
+1 2 3 7
+Normals
+1
+0 0 1
+NormalAtVertices
+1
+1 1
+End
+";
+    let decoded = Mesh::decode_medit(&mut Cursor::new(ss)).unwrap();
+    assert_eq!(decoded.elements().len(), 2);
+    assert_eq!(decoded.elements()[&2], (7, 7, Topology::Triangle3(1, 2, 3)));
+
+    let ss = "MeshVersionFormatted 2\nDimension 3\nSubDomainFromMesh\n1\n3 1 1 0\nEnd\n";
+    assert!(Mesh::decode_medit(&mut Cursor::new(ss)).is_err());
+}
+
+#[test]
+fn su2() {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(1.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (0, 0, Topology::Triangle3(1, 2, 3)));
+    es.insert(2, (0, 0, Topology::Triangle3(1, 3, 4)));
+    es.insert(3, (1, 1, Topology::Line2(1, 2)));
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((1, 1), String::from("physical_2"));
+    let expected = Mesh::new(None, ns, es).with_physical_names(names);
+
+    // Planar meshes keep only two coordinates.
+    let mut bytes = Vec::new();
+    square().encode_su2(&mut bytes).unwrap();
+    assert_eq!(Mesh::decode_su2(&mut Cursor::new(bytes)).unwrap(), expected);
+
+    let ss = "\
+% A single tetrahedron
+NDIME= 3
+NELEM= 1
+10 0 1 2 3 0
+NPOIN= 4 4
+0 0 0 0
+1 0 0 1
+0 1 0 2
+0 0 1 3
+NMARK= 2
+MARKER_TAG= bottom
+MARKER_ELEMS= 1
+5 0 2 1
+MARKER_TAG= side
+MARKER_ELEMS= 1
+5 0 1 3
+";
+    let decoded = Mesh::decode_su2(&mut Cursor::new(ss)).unwrap();
+    assert_eq!(decoded.nodes()[&4], Node::new(0.0, 0.0, 1.0));
+    assert_eq!(
+        decoded.elements()[&1],
+        (0, 0, Topology::Tetrahedron4(1, 2, 3, 4))
+    );
+    assert_eq!(decoded.elements()[&3], (2, 2, Topology::Triangle3(1, 2, 4)));
+    assert_eq!(decoded.physical_names()[&(2, 2)], "side");
+
+    let wide = ss.replace("NDIME= 3", "NDIME= 4");
+    assert!(Mesh::decode_su2(&mut Cursor::new(wide)).is_err());
+    // 266 would wrap around to a tetrahedron.
+    let wrapped = ss.replace("10 0 1 2 3 0", "266 0 1 2 3 0");
+    assert!(Mesh::decode_su2(&mut Cursor::new(wrapped)).is_err());
+    let missing = ss.replace("5 0 1 3", "5 0 1 4");
+    assert!(Mesh::decode_su2(&mut Cursor::new(missing)).is_err());
+    let truncated = &ss[..ss.find("0 0 1 3").unwrap()];
+    assert!(Mesh::decode_su2(&mut Cursor::new(truncated)).is_err());
+}
+
+#[test]
+fn tetgen() {
+    // Planar meshes keep only two coordinates.
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(1.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Triangle3(1, 2, 3)));
+    es.insert(2, (1, 1, Topology::Triangle3(1, 3, 4)));
+    es.insert(3, (2, 2, Topology::Line2(1, 2)));
+    let expected = Mesh::new(None, ns, es);
+
+    let directory = std::env::temp_dir().join(format!("rutile-tetgen-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let base = directory.join("square");
+    square().encode_tetgen(&base).unwrap();
+    assert_eq!(Mesh::decode_tetgen(&base).unwrap(), expected);
+
+    // A TetGen input with zero-based nodes, read from its facets.
+    let base = directory.join("cube");
+    let poly = "\
+# Nodes
+4 3 0 0
+0 0 0 0
+1 1 0 0
+2 0 1 0
+3 0 0 1
+# Facets
+2 1
+1 0 5
+3 0 2 1
+2 1 7
+3 0 1 3
+0 0 0 0
+0
+0
+";
+    std::fs::write(base.with_extension("poly"), poly).unwrap();
+    let decoded = Mesh::decode_tetgen(&base).unwrap();
+    assert_eq!(decoded.nodes()[&4], Node::new(0.0, 0.0, 1.0));
+    assert_eq!(decoded.elements()[&1], (5, 5, Topology::Triangle3(1, 3, 2)));
+    assert_eq!(decoded.elements()[&2], (7, 7, Topology::Triangle3(1, 2, 4)));
+
+    // Once there are elements, facets are left out and quadratic elements
+    // keep their corners.
+    let ele = "1 10 1\n0 0 1 2 3 4 5 6 7 8 9 3\n";
+    std::fs::write(base.with_extension("ele"), ele).unwrap();
+    let decoded = Mesh::decode_tetgen(&base).unwrap();
+    assert_eq!(decoded.elements().len(), 1);
+    assert_eq!(
+        decoded.elements()[&1],
+        (3, 3, Topology::Tetrahedron4(1, 2, 3, 4))
+    );
+
+    // Elements with fewer nodes than corners, and nodes beyond three
+    // dimensions, are errors.
+    std::fs::write(base.with_extension("ele"), "1 2 0\n0 0 1\n").unwrap();
+    assert!(Mesh::decode_tetgen(&base).is_err());
+    std::fs::remove_file(base.with_extension("ele")).unwrap();
+    let wide = poly.replace("4 3 0 0\n0 0 0 0\n", "4 4 0 0\n0 0 0 0 0\n");
+    std::fs::write(base.with_extension("poly"), wide).unwrap();
+    assert!(Mesh::decode_tetgen(&base).is_err());
+    std::fs::remove_dir_all(directory).unwrap();
 }
@@ -0,0 +1,237 @@
+//! Triangle and TetGen files: `.node`, `.ele`, `.face`, `.edge` and `.poly`.
+
+use {
+    crate::{
+        element::{Family, Physical, Topology},
+        mesh::{self, Mesh},
+        node::Node,
+    },
+    std::path::Path,
+};
+
+fn error(message: &str) -> std::io::Error {
+    std::io::Error::other(format!("failed to decode Triangle/TetGen mesh: {message}"))
+}
+
+/// Rows of numbers of a file, without comments and blank lines.
+struct Rows(std::vec::IntoIter<Vec<f64>>);
+
+impl Rows {
+    fn new(text: &str) -> Result<Self, std::io::Error> {
+        let rows = text
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default())
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|v| v.parse().map_err(|_| error("invalid number")))
+                    .collect()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self(rows.into_iter()))
+    }
+
+    fn next(&mut self) -> Result<Vec<f64>, std::io::Error> {
+        self.0.next().ok_or_else(|| error("unexpected end of file"))
+    }
+
+    /// Header with at least `n` values, the first one being a count.
+    fn header(&mut self, n: usize) -> Result<Vec<f64>, std::io::Error> {
+        let mut header = self.next()?;
+        if header.is_empty() {
+            return Err(error("invalid header"));
+        }
+        header.resize(header.len().max(n), 0.0);
+        Ok(header)
+    }
+}
+
+/// Contents of the file `base` with the given `extension`, if it exists.
+fn read(base: &Path, extension: &str) -> Result<Option<String>, std::io::Error> {
+    match std::fs::read_to_string(base.with_extension(extension)) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Mesh under construction, with the offset turning the file indices into
+/// node ids starting at 1.
+struct Builder {
+    nodes: mesh::Nodes,
+    elements: mesh::Elements,
+    dimension: usize,
+    offset: i32,
+}
+
+impl Builder {
+    /// Reads a node section, returning whether it held any node.
+    fn nodes(&mut self, rows: &mut Rows) -> Result<bool, std::io::Error> {
+        let header = rows.header(4)?;
+        let n = header[0] as usize;
+        self.dimension = header[1] as usize;
+        if !(2..=3).contains(&self.dimension) {
+            return Err(error("invalid dimension"));
+        }
+        for index in 0..n {
+            let row = rows.next()?;
+            if row.len() < 1 + self.dimension {
+                return Err(error("invalid node"));
+            }
+            if index == 0 {
+                self.offset = if row[0] == 0.0 { 1 } else { 0 };
+            }
+            let mut p = [0.0; 3];
+            p[..self.dimension].copy_from_slice(&row[1..=self.dimension]);
+            let id = row[0] as i32 + self.offset;
+            self.nodes.insert(id, Node::new(p[0], p[1], p[2]));
+        }
+        Ok(n > 0)
+    }
+
+    fn push(
+        &mut self,
+        family: Family,
+        indices: &[f64],
+        physical: Physical,
+    ) -> Result<(), std::io::Error> {
+        let ids: Vec<i32> = indices.iter().map(|&i| i as i32 + self.offset).collect();
+        if ids.iter().any(|id| !self.nodes.contains_key(id)) {
+            return Err(error("element over a missing node"));
+        }
+        let topology = Topology::new(family, &ids).ok_or_else(|| error("invalid element"))?;
+        let id = self.elements.len() as i32 + 1;
+        self.elements.insert(id, (physical, physical, topology));
+        Ok(())
+    }
+
+    /// Reads elements with `corners` nodes each, tagged with their first
+    /// attribute.
+    fn ele(&mut self, rows: &mut Rows) -> Result<(), std::io::Error> {
+        let header = rows.header(3)?;
+        let (n, per) = (header[0] as usize, header[1] as usize);
+        let attributes = header[2] as usize;
+        let family = match self.dimension {
+            2 => Family::Triangle,
+            _ => Family::Tetrahedron,
+        };
+        if per < family.vertices() {
+            return Err(error("too few nodes per element"));
+        }
+        for _ in 0..n {
+            let row = rows.next()?;
+            if row.len() < 1 + per + attributes {
+                return Err(error("invalid element"));
+            }
+            // Corners come first in quadratic elements.
+            let corners = &row[1..=family.vertices()];
+            let physical = if attributes > 0 {
+                row[1 + per] as Physical
+            } else {
+                0
+            };
+            self.push(family, corners, physical)?;
+        }
+        Ok(())
+    }
+
+    /// Reads faces or edges with their boundary markers.
+    fn markers(&mut self, rows: &mut Rows, family: Family) -> Result<(), std::io::Error> {
+        let header = rows.header(2)?;
+        let (n, marked) = (header[0] as usize, header[1] != 0.0);
+        let corners = family.vertices();
+        for _ in 0..n {
+            let row = rows.next()?;
+            if row.len() < 1 + corners + usize::from(marked) {
+                return Err(error("invalid boundary element"));
+            }
+            let physical = if marked {
+                row[1 + corners] as Physical
+            } else {
+                0
+            };
+            self.push(family, &row[1..=corners], physical)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the facets of a TetGen `.poly` file, keeping the polygons that
+    /// are segments, triangles or quadrangles.
+    fn facets(&mut self, rows: &mut Rows) -> Result<(), std::io::Error> {
+        let header = rows.header(2)?;
+        let (n, marked) = (header[0] as usize, header[1] != 0.0);
+        for _ in 0..n {
+            let mut facet = rows.next()?;
+            facet.resize(3, 0.0);
+            let (polygons, holes) = (facet[0] as usize, facet[1] as usize);
+            let physical = if marked { facet[2] as Physical } else { 0 };
+            for _ in 0..polygons {
+                let polygon = rows.next()?;
+                let k = polygon.first().map_or(0, |&k| k as usize);
+                let family = match k {
+                    2 => Family::Line,
+                    3 => Family::Triangle,
+                    4 => Family::Quadrangle,
+                    _ => continue,
+                };
+                let corners = polygon.get(1..=k).ok_or_else(|| error("invalid polygon"))?;
+                self.push(family, corners, physical)?;
+            }
+            for _ in 0..holes {
+                rows.next()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Mesh {
+    /// Reads the Triangle or TetGen files sharing `base`: the nodes of
+    /// `<base>.node`, or of `<base>.poly` when it lists them, then the
+    /// elements of `<base>.ele`, `<base>.face` and `<base>.edge`, whichever
+    /// exist, and the segments or facets of `<base>.poly` when there is no
+    /// `.ele` file.
+    ///
+    /// Nodes keep their indices, shifted to start at 1 when they start at 0;
+    /// elements are numbered from 1 in that order. The first element
+    /// attribute and the boundary markers become physical and elementary
+    /// tags; quadratic elements keep their corners.
+    pub fn decode_tetgen<P: AsRef<Path>>(base: P) -> Result<Self, std::io::Error> {
+        let base = base.as_ref();
+        let mut builder = Builder {
+            nodes: mesh::Nodes::new(),
+            elements: mesh::Elements::new(),
+            dimension: 3,
+            offset: 0,
+        };
+
+        let poly = read(base, "poly")?;
+        let mut poly = poly.as_deref().map(Rows::new).transpose()?;
+        let listed = match &mut poly {
+            Some(rows) => builder.nodes(rows)?,
+            None => false,
+        };
+        if !listed {
+            let text = read(base, "node")?.ok_or_else(|| error("missing nodes"))?;
+            builder.nodes(&mut Rows::new(&text)?)?;
+        }
+
+        let ele = read(base, "ele")?;
+        if let Some(text) = &ele {
+            builder.ele(&mut Rows::new(text)?)?;
+        }
+        if let Some(text) = read(base, "face")? {
+            builder.markers(&mut Rows::new(&text)?, Family::Triangle)?;
+        }
+        if let Some(text) = read(base, "edge")? {
+            builder.markers(&mut Rows::new(&text)?, Family::Line)?;
+        }
+        if let (Some(rows), None) = (&mut poly, &ele) {
+            match builder.dimension {
+                2 => builder.markers(rows, Family::Line)?,
+                _ => builder.facets(rows)?,
+            }
+        }
+        Ok(Mesh::new(None, builder.nodes, builder.elements))
+    }
+}
@@ -16,9 +16,11 @@ use {
         sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
         IResult,
     },
+    std::collections::HashMap,
 };
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Format {
     pub(crate) version: String,
     pub(crate) file: i32,
@@ -26,6 +28,7 @@ pub struct Format {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct Position {
     pub(crate) x: f64,
     pub(crate) y: f64,
@@ -36,6 +39,7 @@ pub(crate) struct Position {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeEntity {
     pub(crate) dim: i32,
     pub(crate) tag: Id,
@@ -44,6 +48,7 @@ pub struct NodeEntity {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Nodes {
     pub(crate) min_node: Id,
     pub(crate) max_node: Id,
@@ -52,12 +57,14 @@ pub struct Nodes {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaggedTopology {
     pub(crate) tag: i32,
     pub(crate) topology: Topology,
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElementEntity {
     pub(crate) dim: i32,
     pub(crate) tag: Id,
@@ -65,6 +72,7 @@ pub struct ElementEntity {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Elements {
     pub(crate) num_elements: u64,
     pub(crate) min_tag: Id,
@@ -73,6 +81,7 @@ pub struct Elements {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PhysicalName {
     pub(crate) dimension: i32,
     pub(crate) tag: Id,
@@ -82,6 +91,7 @@ pub struct PhysicalName {
 pub(crate) type PhysicalNames = Vec<PhysicalName>;
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntityPoint {
     pub(crate) tag: Id,
     pub(crate) x: f64,
@@ -91,6 +101,7 @@ pub struct EntityPoint {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntityCurve {
     pub(crate) tag: Id,
     pub(crate) min_x: f64,
@@ -104,6 +115,7 @@ pub struct EntityCurve {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntitySurface {
     pub(crate) tag: Id,
     pub(crate) min_x: f64,
@@ -117,6 +129,7 @@ pub struct EntitySurface {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntityVolume {
     pub(crate) tag: Id,
     pub(crate) min_x: f64,
@@ -130,6 +143,7 @@ pub struct EntityVolume {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Entities {
     pub(crate) points: Vec<EntityPoint>,
     pub(crate) curves: Vec<EntityCurve>,
@@ -138,12 +152,14 @@ pub struct Entities {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntityGhost {
     pub(crate) tag: Id,
     pub(crate) partition: Id,
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PartitionedEntityPoint {
     pub(crate) tag: Id,
     pub(crate) parent_dim: i32,
@@ -156,6 +172,7 @@ pub struct PartitionedEntityPoint {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PartitionedEntityCurve {
     pub(crate) tag: Id,
     pub(crate) parent_dim: i32,
@@ -172,6 +189,7 @@ pub struct PartitionedEntityCurve {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PartitionedEntitySurface {
     pub(crate) tag: Id,
     pub(crate) parent_dim: i32,
@@ -188,6 +206,7 @@ pub struct PartitionedEntitySurface {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PartitionedEntityVolume {
     pub(crate) tag: Id,
     pub(crate) parent_dim: i32,
@@ -204,6 +223,7 @@ pub struct PartitionedEntityVolume {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PartitionedEntities {
     pub(crate) num_partitions: u64,
     pub(crate) ghosts: Vec<EntityGhost>,
@@ -214,12 +234,14 @@ pub struct PartitionedEntities {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Parametrisations {
     pub(crate) curves: Vec<ParametrisationsCurve>,
     pub(crate) surfaces: Vec<ParametrisationsSurface>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParametrisationsCurveNode {
     pub(crate) x: f64,
     pub(crate) y: f64,
@@ -228,12 +250,14 @@ pub struct ParametrisationsCurveNode {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParametrisationsCurve {
     pub(crate) tag: Id,
     pub(crate) curves: Vec<ParametrisationsCurveNode>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParametrisationsSurfaceNode {
     pub(crate) x: f64,
     pub(crate) y: f64,
@@ -249,9 +273,11 @@ pub struct ParametrisationsSurfaceNode {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParametrisationsSurfaceTriangle(pub(crate) Id, pub(crate) Id, pub(crate) Id);
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParametrisationsSurface {
     pub(crate) tag: Id,
     pub(crate) nodes: Vec<ParametrisationsSurfaceNode>,
@@ -259,12 +285,14 @@ pub struct ParametrisationsSurface {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PeriodicLinkNode {
     pub(crate) tag: Id,
     pub(crate) master: Id,
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PeriodicLink {
     pub(crate) dim: i32,
     pub(crate) tag: Id,
@@ -274,11 +302,13 @@ pub struct PeriodicLink {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Periodic {
     pub(crate) links: Vec<PeriodicLink>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mesh {
     pub(crate) format: Format,
     pub(crate) entities: Option<Entities>,
@@ -291,14 +321,15 @@ pub struct Mesh {
 }
 
 impl Mesh {
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_legacy(self) -> mesh::Mesh {
         let Self {
             format,
             nodes: onodes,
             elements: oelements,
-            entities: _,
+            entities,
             partitioned_entities: _,
-            physical_names: _,
+            physical_names,
             periodic: _,
             parametrisations: _,
         } = self;
@@ -310,14 +341,40 @@ impl Mesh {
                 nodes.insert(id, crate::node::Node::new(n.x, n.y, n.z));
             }
         }
+        let physicals = entities.map(|e| e.physicals()).unwrap_or_default();
         let mut elements: mesh::Elements = Default::default();
         for element in oelements.entities.into_iter() {
-            let tag = element.tag;
+            let elementary = element.tag;
+            let physical = physicals
+                .get(&(element.dim, elementary))
+                .copied()
+                .unwrap_or(0);
             for element in element.elements {
-                elements.insert(tag, (0, 0, element.topology));
+                elements.insert(element.tag, (physical, elementary, element.topology));
             }
         }
-        mesh::Mesh::new(Some(format), nodes, elements)
+        let physical_names = physical_names
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| ((p.dimension, p.tag), p.name))
+            .collect();
+        mesh::Mesh::new(Some(format), nodes, elements).with_physical_names(physical_names)
+    }
+}
+
+impl Entities {
+    /// First physical tag of each entity, keyed by its dimension and tag.
+    fn physicals(&self) -> HashMap<(i32, Id), Id> {
+        let points = self.points.iter().map(|e| (0, e.tag, &e.physical_tags));
+        let curves = self.curves.iter().map(|e| (1, e.tag, &e.physical_tags));
+        let surfaces = self.surfaces.iter().map(|e| (2, e.tag, &e.physical_tags));
+        let volumes = self.volumes.iter().map(|e| (3, e.tag, &e.physical_tags));
+        points
+            .chain(curves)
+            .chain(surfaces)
+            .chain(volumes)
+            .filter_map(|(dim, tag, physicals)| Some(((dim, tag), *physicals.first()?)))
+            .collect()
     }
 }
 
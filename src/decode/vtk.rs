@@ -0,0 +1,269 @@
+//! Legacy VTK unstructured grids, in ASCII.
+
+use {
+    crate::{
+        element::{Family, Topology},
+        mesh::{self, Mesh},
+        node::Node,
+    },
+    std::{collections::HashMap, io::Read},
+};
+
+/// Family of the elements of VTK cell type `cell`, for the linear cells.
+pub(crate) const fn family(cell: u8) -> Option<Family> {
+    let family = match cell {
+        1 => Family::Point,
+        3 => Family::Line,
+        5 => Family::Triangle,
+        9 => Family::Quadrangle,
+        10 => Family::Tetrahedron,
+        12 => Family::Hexahedron,
+        13 => Family::Prism,
+        14 => Family::Pyramid,
+        _ => return None,
+    };
+    Some(family)
+}
+
+pub(crate) fn error(message: &str) -> std::io::Error {
+    std::io::Error::other(format!("failed to decode VTK mesh: {message}"))
+}
+
+/// Mesh over `points`, three coordinates each, and `cells` of zero-based
+/// point indices, numbering both from 1.
+///
+/// Physical and elementary tags come from the `gmsh:physical` and
+/// `gmsh:geometrical` cell arrays, or from `CellEntityIds` for the physical
+/// ones, and default to 0.
+pub(crate) fn mesh(
+    points: &[f64],
+    cells: &[(u8, Vec<usize>)],
+    arrays: &HashMap<String, Vec<f64>>,
+) -> Result<Mesh, std::io::Error> {
+    // One-based id of the zero-based `index`.
+    let id = |index: usize| index.checked_add(1).and_then(|i| i32::try_from(i).ok());
+    let mut nodes = mesh::Nodes::new();
+    for (index, p) in points.chunks_exact(3).enumerate() {
+        let id = id(index).ok_or_else(|| error("too many points"))?;
+        nodes.insert(id, Node::new(p[0], p[1], p[2]));
+    }
+
+    let tags = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| {
+                arrays.get(*name).or_else(|| {
+                    arrays
+                        .iter()
+                        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                        .map(|(_, tags)| tags)
+                })
+            })
+            .filter(|tags| tags.len() == cells.len())
+    };
+    let physical = tags(&["gmsh:physical", "CellEntityIds"]);
+    let elementary = tags(&["gmsh:geometrical"]);
+
+    let mut elements = mesh::Elements::new();
+    for (index, (cell, connectivity)) in cells.iter().enumerate() {
+        let family = family(*cell).ok_or_else(|| error("unsupported cell type"))?;
+        let ids: Vec<i32> = connectivity
+            .iter()
+            .map(|&i| id(i).filter(|id| nodes.contains_key(id)))
+            .collect::<Option<_>>()
+            .ok_or_else(|| error("cell over a missing point"))?;
+        let topology =
+            Topology::new(family, &ids).ok_or_else(|| error("wrong number of cell points"))?;
+        let tag = |tags: Option<&Vec<f64>>| tags.map_or(0, |t| t[index] as i32);
+        let id = id(index).ok_or_else(|| error("too many cells"))?;
+        elements.insert(id, (tag(physical), tag(elementary), topology));
+    }
+    Ok(Mesh::new(None, nodes, elements))
+}
+
+/// Whitespace separated tokens of the body of a legacy file.
+struct Tokens<'a>(std::iter::Peekable<std::str::SplitWhitespace<'a>>);
+
+impl<'a> Tokens<'a> {
+    fn next(&mut self) -> Result<&'a str, std::io::Error> {
+        self.0.next().ok_or_else(|| error("unexpected end of file"))
+    }
+
+    fn count(&mut self) -> Result<usize, std::io::Error> {
+        self.next()?.parse().map_err(|_| error("invalid count"))
+    }
+
+    /// Product of counts, as the size of an array.
+    fn size(a: usize, b: usize) -> Result<usize, std::io::Error> {
+        a.checked_mul(b).ok_or_else(|| error("invalid count"))
+    }
+
+    fn values(&mut self, n: usize) -> Result<Vec<f64>, std::io::Error> {
+        (0..n)
+            .map(|_| self.next()?.parse().map_err(|_| error("invalid number")))
+            .collect()
+    }
+
+    fn indices(&mut self, n: usize) -> Result<Vec<usize>, std::io::Error> {
+        (0..n).map(|_| self.count()).collect()
+    }
+}
+
+/// Arrays of a `CELL_DATA` or `POINT_DATA` section over `n` tuples; FIELD
+/// arrays and SCALARS keep their names, other attributes are skipped.
+fn attributes(
+    tokens: &mut Tokens,
+    n: usize,
+    arrays: &mut HashMap<String, Vec<f64>>,
+) -> Result<(), std::io::Error> {
+    while let Some(keyword) = tokens.0.peek() {
+        let keyword = keyword.to_ascii_uppercase();
+        match keyword.as_str() {
+            "FIELD" => {
+                tokens.next()?;
+                tokens.next()?;
+                for _ in 0..tokens.count()? {
+                    let name = tokens.next()?.to_string();
+                    let components = tokens.count()?;
+                    let tuples = tokens.count()?;
+                    tokens.next()?;
+                    arrays.insert(name, tokens.values(Tokens::size(components, tuples)?)?);
+                }
+            }
+            "SCALARS" => {
+                tokens.next()?;
+                let name = tokens.next()?.to_string();
+                tokens.next()?;
+                let table = |t: &&str| t.eq_ignore_ascii_case("LOOKUP_TABLE");
+                let components = match tokens.0.peek() {
+                    Some(t) if table(t) => 1,
+                    _ => tokens.count()?,
+                };
+                if tokens.0.peek().is_some_and(table) {
+                    tokens.next()?;
+                    tokens.next()?;
+                }
+                arrays.insert(name, tokens.values(Tokens::size(components, n)?)?);
+            }
+            "VECTORS" | "NORMALS" | "TENSORS" => {
+                tokens.next()?;
+                tokens.next()?;
+                tokens.next()?;
+                let components = if keyword == "TENSORS" { 9 } else { 3 };
+                tokens.values(Tokens::size(components, n)?)?;
+            }
+            "COLOR_SCALARS" => {
+                tokens.next()?;
+                tokens.next()?;
+                let components = tokens.count()?;
+                tokens.values(Tokens::size(components, n)?)?;
+            }
+            "TEXTURE_COORDINATES" => {
+                tokens.next()?;
+                tokens.next()?;
+                let components = tokens.count()?;
+                tokens.next()?;
+                tokens.values(Tokens::size(components, n)?)?;
+            }
+            "LOOKUP_TABLE" => {
+                tokens.next()?;
+                tokens.next()?;
+                let size = tokens.count()?;
+                tokens.values(Tokens::size(4, size)?)?;
+            }
+            _ => return Ok(()),
+        }
+    }
+    Ok(())
+}
+
+impl Mesh {
+    /// Reads an ASCII legacy VTK unstructured grid, in either the classic or
+    /// the 5.1 cell layout.
+    ///
+    /// Points and cells are numbered from 1 in file order; see `decode_vtu`
+    /// for the tags.
+    pub fn decode_vtk<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let mut ss = String::new();
+        reader.read_to_string(&mut ss)?;
+
+        let mut lines = ss.lines();
+        let header = lines.next().unwrap_or_default();
+        if !header.starts_with("# vtk DataFile") {
+            return Err(error("missing header"));
+        }
+        lines.next();
+        if lines.next().map(str::trim) != Some("ASCII") {
+            return Err(error("only ASCII files are supported"));
+        }
+        let body: Vec<&str> = lines.collect();
+        let body = body.join("\n");
+        let mut tokens = Tokens(body.split_whitespace().peekable());
+
+        let mut points = Vec::new();
+        let mut connectivity: Vec<Vec<usize>> = Vec::new();
+        let mut types = Vec::new();
+        let mut arrays = HashMap::new();
+        while let Some(keyword) = tokens.0.next() {
+            match keyword.to_ascii_uppercase().as_str() {
+                "DATASET" => match tokens.next()? {
+                    "UNSTRUCTURED_GRID" => {}
+                    _ => return Err(error("only unstructured grids are supported")),
+                },
+                "POINTS" => {
+                    let n = tokens.count()?;
+                    tokens.next()?;
+                    points = tokens.values(Tokens::size(3, n)?)?;
+                }
+                "CELLS" => {
+                    let n = tokens.count()?;
+                    let size = tokens.count()?;
+                    if tokens.0.peek() == Some(&"OFFSETS") {
+                        tokens.next()?;
+                        tokens.next()?;
+                        let offsets = tokens.indices(n)?;
+                        tokens.next()?;
+                        tokens.next()?;
+                        let indices = tokens.indices(size)?;
+                        connectivity = offsets
+                            .windows(2)
+                            .map(|w| indices.get(w[0]..w[1]).map(<[usize]>::to_vec))
+                            .collect::<Option<_>>()
+                            .ok_or_else(|| error("invalid offsets"))?;
+                    } else {
+                        connectivity = (0..n)
+                            .map(|_| {
+                                let count = tokens.count()?;
+                                tokens.indices(count)
+                            })
+                            .collect::<Result<_, _>>()?;
+                    }
+                }
+                "CELL_TYPES" => {
+                    let n = tokens.count()?;
+                    types = tokens
+                        .indices(n)?
+                        .into_iter()
+                        .map(|t| u8::try_from(t).map_err(|_| error("unsupported cell type")))
+                        .collect::<Result<_, _>>()?;
+                }
+                "CELL_DATA" => {
+                    let n = tokens.count()?;
+                    attributes(&mut tokens, n, &mut arrays)?;
+                }
+                "POINT_DATA" => {
+                    let n = tokens.count()?;
+                    attributes(&mut tokens, n, &mut HashMap::new())?;
+                }
+                // Metadata runs until the next section.
+                _ => {}
+            }
+        }
+
+        if types.len() != connectivity.len() {
+            return Err(error("cell count mismatch"));
+        }
+        let cells: Vec<(u8, Vec<usize>)> = types.into_iter().zip(connectivity).collect();
+        mesh(&points, &cells, &arrays)
+    }
+}
@@ -0,0 +1,328 @@
+//! VTK XML unstructured grids, with ASCII, base64 or appended data arrays.
+
+use {
+    crate::{
+        base64,
+        decode::vtk::{self, error},
+        mesh::Mesh,
+    },
+    std::{collections::HashMap, io::Read},
+};
+
+/// Start tag of an XML element, with the position following it.
+#[derive(Clone, Debug, PartialEq)]
+struct Tag<'a> {
+    name: &'a str,
+    attributes: HashMap<&'a str, String>,
+    end: usize,
+}
+
+impl Tag<'_> {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+}
+
+/// Start and end tags of `xml`, in document order; end tags have names
+/// starting with `/`.
+fn tags(xml: &str) -> Result<Vec<Tag<'_>>, std::io::Error> {
+    let mut tags = Vec::new();
+    let mut rest = 0;
+    while let Some(offset) = xml[rest..].find('<') {
+        let start = rest + offset + 1;
+        let end = start
+            + xml[start..]
+                .find('>')
+                .ok_or_else(|| error("unterminated tag"))?;
+        rest = end + 1;
+        let body = &xml[start..end];
+        if body.starts_with('?') || body.starts_with('!') {
+            continue;
+        }
+        let body = body.trim_end_matches('/');
+        let (name, mut attributes) = body.split_once(char::is_whitespace).unwrap_or((body, ""));
+        let mut tag = Tag {
+            name,
+            attributes: HashMap::new(),
+            end: rest,
+        };
+        while let Some((key, value)) = attributes.split_once('=') {
+            let value = value.trim_start();
+            let quote = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => return Err(error("invalid attribute")),
+            };
+            let length = value[1..]
+                .find(quote)
+                .ok_or_else(|| error("unterminated attribute"))?;
+            tag.attributes
+                .insert(key.trim(), unescape(&value[1..1 + length]));
+            attributes = &value[length + 2..];
+        }
+        tags.push(tag);
+    }
+    Ok(tags)
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Layout of the binary data blocks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Layout {
+    /// Size in bytes of the header giving the length of each block.
+    header: usize,
+    big: bool,
+}
+
+impl Layout {
+    fn length(&self, bytes: &[u8]) -> Option<usize> {
+        let mut header = bytes.get(..self.header)?.to_vec();
+        if self.big {
+            header.reverse();
+        }
+        header.resize(8, 0);
+        let length = u64::from_le_bytes(header.try_into().ok()?);
+        usize::try_from(length).ok()
+    }
+
+    /// Values of type `kind` stored in `bytes`.
+    fn values(&self, kind: &str, bytes: &[u8]) -> Result<Vec<f64>, std::io::Error> {
+        let size = match kind {
+            "Int8" | "UInt8" => 1,
+            "Int16" | "UInt16" => 2,
+            "Int32" | "UInt32" | "Float32" => 4,
+            "Int64" | "UInt64" | "Float64" => 8,
+            _ => return Err(error("unsupported data type")),
+        };
+        let values = bytes.chunks_exact(size).map(|chunk| {
+            let mut b = chunk.to_vec();
+            if self.big {
+                b.reverse();
+            }
+            match kind {
+                "Int8" => i8::from_le_bytes([b[0]]) as f64,
+                "UInt8" => b[0] as f64,
+                "Int16" => i16::from_le_bytes([b[0], b[1]]) as f64,
+                "UInt16" => u16::from_le_bytes([b[0], b[1]]) as f64,
+                "Int32" => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                "UInt32" => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                "Float32" => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                "Int64" => {
+                    i64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f64
+                }
+                "UInt64" => {
+                    u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f64
+                }
+                _ => f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]),
+            }
+        });
+        Ok(values.collect())
+    }
+
+    /// Data of the block, header included, at the start of `bytes`.
+    fn block<'a>(&self, bytes: &'a [u8]) -> Result<&'a [u8], std::io::Error> {
+        let length = self.length(bytes).ok_or_else(|| error("truncated data"))?;
+        let end = self
+            .header
+            .checked_add(length)
+            .ok_or_else(|| error("truncated data"))?;
+        bytes
+            .get(self.header..end)
+            .ok_or_else(|| error("truncated data"))
+    }
+
+    /// Data of the block at the start of base64 `text`, whose header may have
+    /// been encoded on its own or together with the data.
+    fn encoded(&self, text: &str) -> Result<Vec<u8>, std::io::Error> {
+        let invalid = || error("invalid base64 data");
+        let prefix = self.header.div_ceil(3) * 4;
+        let head = text.get(..prefix).ok_or_else(invalid)?;
+        let header = base64::decode(head).ok_or_else(invalid)?;
+        let length = self.length(&header).ok_or_else(invalid)?;
+        // Base64 length of `n` bytes.
+        let size = |n: usize| n.div_ceil(3).checked_mul(4).ok_or_else(invalid);
+        let bytes = if head.ends_with('=') {
+            let text = &text[prefix..];
+            let body = text.get(..size(length)?).ok_or_else(invalid)?;
+            let mut bytes = header[..self.header].to_vec();
+            bytes.extend(base64::decode(body).ok_or_else(invalid)?);
+            bytes
+        } else {
+            let length = self.header.checked_add(length).ok_or_else(invalid)?;
+            let body = text.get(..size(length)?).ok_or_else(invalid)?;
+            base64::decode(body).ok_or_else(invalid)?
+        };
+        Ok(self.block(&bytes)?.to_vec())
+    }
+}
+
+/// Appended data section, raw or base64.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Appended<'a> {
+    Raw(&'a [u8]),
+    Base64(&'a str),
+}
+
+/// Values of the data array starting with `tag`.
+fn array(
+    xml: &str,
+    tag: &Tag,
+    layout: Layout,
+    appended: Option<Appended>,
+) -> Result<Vec<f64>, std::io::Error> {
+    let kind = tag.attribute("type").unwrap_or("Float32");
+    let content = xml[tag.end..]
+        .find("</DataArray>")
+        .map_or("", |end| &xml[tag.end..tag.end + end]);
+    match tag.attribute("format").unwrap_or("ascii") {
+        "ascii" => content
+            .split_whitespace()
+            .map(|v| v.parse().map_err(|_| error("invalid number")))
+            .collect(),
+        "binary" => {
+            let bytes = base64::decode(content).ok_or_else(|| error("invalid base64 data"))?;
+            layout.values(kind, layout.block(&bytes)?)
+        }
+        "appended" => {
+            let offset: usize = tag
+                .attribute("offset")
+                .and_then(|o| o.parse().ok())
+                .ok_or_else(|| error("missing offset"))?;
+            match appended.ok_or_else(|| error("missing appended data"))? {
+                Appended::Raw(bytes) => {
+                    let bytes = bytes.get(offset..).ok_or_else(|| error("invalid offset"))?;
+                    layout.values(kind, layout.block(bytes)?)
+                }
+                Appended::Base64(text) => {
+                    let text = text.get(offset..).ok_or_else(|| error("invalid offset"))?;
+                    layout.values(kind, &layout.encoded(text)?)
+                }
+            }
+        }
+        _ => Err(error("unsupported array format")),
+    }
+}
+
+impl Mesh {
+    /// Reads a VTK XML unstructured grid, `.vtu`, with a single piece and
+    /// uncompressed data arrays.
+    ///
+    /// Points and cells are numbered from 1 in file order. Physical and
+    /// elementary tags come from the `gmsh:physical` and `gmsh:geometrical`
+    /// cell data, or `CellEntityIds` for the physical ones, and default to 0.
+    /// Array names are matched regardless of case.
+    pub fn decode_vtu<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        // Raw appended data is binary, only the markup before it is text.
+        let marker = b"<AppendedData";
+        let split = bytes
+            .windows(marker.len())
+            .position(|w| w == marker)
+            .unwrap_or(bytes.len());
+        let xml = std::str::from_utf8(&bytes[..split]).map_err(|_| error("invalid text"))?;
+        let appended = match split < bytes.len() {
+            true => {
+                let rest = &bytes[split..];
+                let close = rest
+                    .iter()
+                    .position(|&b| b == b'>')
+                    .ok_or_else(|| error("unterminated tag"))?;
+                let head =
+                    std::str::from_utf8(&rest[..=close]).map_err(|_| error("invalid text"))?;
+                let tag = tags(head)?.remove(0);
+                let underscore = rest[close..]
+                    .iter()
+                    .position(|&b| b == b'_')
+                    .ok_or_else(|| error("missing appended data"))?;
+                let data = &rest[close + underscore + 1..];
+                Some(match tag.attribute("encoding") {
+                    Some("raw") => Appended::Raw(data),
+                    _ => {
+                        let end = data.iter().position(|&b| b == b'<').unwrap_or(data.len());
+                        let text = std::str::from_utf8(&data[..end])
+                            .map_err(|_| error("invalid base64 data"))?;
+                        Appended::Base64(text.trim_end())
+                    }
+                })
+            }
+            false => None,
+        };
+
+        let tags = tags(xml)?;
+        let file = tags
+            .iter()
+            .find(|t| t.name == "VTKFile")
+            .ok_or_else(|| error("missing VTKFile element"))?;
+        if file.attribute("type") != Some("UnstructuredGrid") {
+            return Err(error("only unstructured grids are supported"));
+        }
+        if file.attribute("compressor").is_some() {
+            return Err(error("compressed data is not supported"));
+        }
+        let layout = Layout {
+            header: match file.attribute("header_type") {
+                Some("UInt64") => 8,
+                _ => 4,
+            },
+            big: file.attribute("byte_order") == Some("BigEndian"),
+        };
+        if tags.iter().filter(|t| t.name == "Piece").count() != 1 {
+            return Err(error("only single piece files are supported"));
+        }
+
+        let mut section = "";
+        let mut points = Vec::new();
+        let mut cells: HashMap<String, Vec<f64>> = HashMap::new();
+        let mut arrays = HashMap::new();
+        for tag in &tags {
+            match tag.name {
+                "Points" | "Cells" | "PointData" | "CellData" => section = tag.name,
+                "/Points" | "/Cells" | "/PointData" | "/CellData" => section = "",
+                "DataArray" => {
+                    let name = tag.attribute("Name").unwrap_or_default().to_string();
+                    match section {
+                        "Points" => points = array(xml, tag, layout, appended)?,
+                        "Cells" => {
+                            let name = name.to_ascii_lowercase();
+                            cells.insert(name, array(xml, tag, layout, appended)?);
+                        }
+                        "CellData" => {
+                            arrays.insert(name, array(xml, tag, layout, appended)?);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let missing = || error("missing cell arrays");
+        let connectivity = cells.get("connectivity").ok_or_else(missing)?;
+        let offsets = cells.get("offsets").ok_or_else(missing)?;
+        let types = cells.get("types").ok_or_else(missing)?;
+        if offsets.len() != types.len() {
+            return Err(error("cell count mismatch"));
+        }
+        let mut start = 0;
+        let mut list = Vec::with_capacity(types.len());
+        for (&offset, &cell) in offsets.iter().zip(types) {
+            let end = offset as usize;
+            let indices = connectivity
+                .get(start..end)
+                .ok_or_else(|| error("invalid offsets"))?;
+            let cell = u8::try_from(cell as i64).map_err(|_| error("unsupported cell type"))?;
+            list.push((cell, indices.iter().map(|&i| i as usize).collect()));
+            start = end;
+        }
+        vtk::mesh(&points, &list, &arrays)
+    }
+}
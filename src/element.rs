@@ -5,6 +5,7 @@ pub(crate) type Physical = i32;
 pub(crate) type Elementary = i32;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Topology {
     Line2(node::Id, node::Id),
     Triangle3(node::Id, node::Id, node::Id),
@@ -24,3 +25,216 @@ pub enum Topology {
     Pyramid5(node::Id, node::Id, node::Id, node::Id, node::Id),
     Point1(node::Id),
 }
+
+impl Topology {
+    /// Element of the given `family` over `nodes`, in gmsh ordering.
+    ///
+    /// Returns `None` if the number of nodes does not match the family.
+    pub fn new(family: Family, nodes: &[node::Id]) -> Option<Self> {
+        let topology = match (family, nodes) {
+            (Family::Point, &[x0]) => Self::Point1(x0),
+            (Family::Line, &[x0, x1]) => Self::Line2(x0, x1),
+            (Family::Triangle, &[x0, x1, x2]) => Self::Triangle3(x0, x1, x2),
+            (Family::Quadrangle, &[x0, x1, x2, x3]) => Self::Quadrangle4(x0, x1, x2, x3),
+            (Family::Tetrahedron, &[x0, x1, x2, x3]) => Self::Tetrahedron4(x0, x1, x2, x3),
+            (Family::Hexahedron, &[x0, x1, x2, x3, x4, x5, x6, x7]) => {
+                Self::Hexahedron8(x0, x1, x2, x3, x4, x5, x6, x7)
+            }
+            (Family::Prism, &[x0, x1, x2, x3, x4, x5]) => Self::Prism6(x0, x1, x2, x3, x4, x5),
+            (Family::Pyramid, &[x0, x1, x2, x3, x4]) => Self::Pyramid5(x0, x1, x2, x3, x4),
+            _ => return None,
+        };
+        Some(topology)
+    }
+
+    pub const fn family(&self) -> Family {
+        match self {
+            Self::Point1(..) => Family::Point,
+            Self::Line2(..) => Family::Line,
+            Self::Triangle3(..) => Family::Triangle,
+            Self::Quadrangle4(..) => Family::Quadrangle,
+            Self::Tetrahedron4(..) => Family::Tetrahedron,
+            Self::Hexahedron8(..) => Family::Hexahedron,
+            Self::Prism6(..) => Family::Prism,
+            Self::Pyramid5(..) => Family::Pyramid,
+        }
+    }
+
+    pub const fn dimension(&self) -> i32 {
+        self.family().dimension()
+    }
+
+    /// Node ids in gmsh ordering.
+    pub fn nodes(&self) -> Vec<node::Id> {
+        match *self {
+            Self::Point1(x0) => vec![x0],
+            Self::Line2(x0, x1) => vec![x0, x1],
+            Self::Triangle3(x0, x1, x2) => vec![x0, x1, x2],
+            Self::Quadrangle4(x0, x1, x2, x3) => vec![x0, x1, x2, x3],
+            Self::Tetrahedron4(x0, x1, x2, x3) => vec![x0, x1, x2, x3],
+            Self::Hexahedron8(x0, x1, x2, x3, x4, x5, x6, x7) => {
+                vec![x0, x1, x2, x3, x4, x5, x6, x7]
+            }
+            Self::Prism6(x0, x1, x2, x3, x4, x5) => vec![x0, x1, x2, x3, x4, x5],
+            Self::Pyramid5(x0, x1, x2, x3, x4) => vec![x0, x1, x2, x3, x4],
+        }
+    }
+
+    /// Same element over the nodes given by `f` for each of its nodes.
+    pub fn map_nodes<F: FnMut(node::Id) -> node::Id>(&self, f: F) -> Self {
+        let nodes: Vec<node::Id> = self.nodes().into_iter().map(f).collect();
+        match Self::new(self.family(), &nodes) {
+            Some(topology) => topology,
+            None => unreachable!("node count is preserved"),
+        }
+    }
+
+    /// Node ids of each edge, in the order of `Family::edges`.
+    pub fn edges(&self) -> Vec<[node::Id; 2]> {
+        let nodes = self.nodes();
+        self.family()
+            .edges()
+            .iter()
+            .map(|&[a, b]| [nodes[a], nodes[b]])
+            .collect()
+    }
+
+    /// Node ids of each face, in the order of `Family::faces`.
+    pub fn faces(&self) -> Vec<Vec<node::Id>> {
+        let nodes = self.nodes();
+        self.family()
+            .faces()
+            .iter()
+            .map(|face| face.iter().map(|&v| nodes[v]).collect())
+            .collect()
+    }
+
+    /// Node ids of the sub-entities one dimension below the element: faces of
+    /// volumes, edges of surfaces and vertices of lines.
+    pub fn facets(&self) -> Vec<Vec<node::Id>> {
+        match self.dimension() {
+            3 => self.faces(),
+            2 => self.edges().into_iter().map(Vec::from).collect(),
+            1 => self.nodes().into_iter().map(|n| vec![n]).collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// Reference element shared by every `Topology` of the same kind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Family {
+    Point,
+    Line,
+    Triangle,
+    Quadrangle,
+    Tetrahedron,
+    Hexahedron,
+    Prism,
+    Pyramid,
+}
+
+impl Family {
+    pub const fn dimension(&self) -> i32 {
+        match self {
+            Self::Point => 0,
+            Self::Line => 1,
+            Self::Triangle | Self::Quadrangle => 2,
+            Self::Tetrahedron | Self::Hexahedron | Self::Prism | Self::Pyramid => 3,
+        }
+    }
+
+    pub const fn vertices(&self) -> usize {
+        match self {
+            Self::Point => 1,
+            Self::Line => 2,
+            Self::Triangle => 3,
+            Self::Quadrangle | Self::Tetrahedron => 4,
+            Self::Pyramid => 5,
+            Self::Prism => 6,
+            Self::Hexahedron => 8,
+        }
+    }
+
+    /// Local vertex pairs of each edge, following the gmsh reference element.
+    pub const fn edges(&self) -> &'static [[usize; 2]] {
+        match self {
+            Self::Point => &[],
+            Self::Line => &[[0, 1]],
+            Self::Triangle => &[[0, 1], [1, 2], [2, 0]],
+            Self::Quadrangle => &[[0, 1], [1, 2], [2, 3], [3, 0]],
+            Self::Tetrahedron => &[[0, 1], [1, 2], [2, 0], [3, 0], [3, 2], [3, 1]],
+            Self::Hexahedron => &[
+                [0, 1],
+                [0, 3],
+                [0, 4],
+                [1, 2],
+                [1, 5],
+                [2, 3],
+                [2, 6],
+                [3, 7],
+                [4, 5],
+                [4, 7],
+                [5, 6],
+                [6, 7],
+            ],
+            Self::Prism => &[
+                [0, 1],
+                [0, 2],
+                [0, 3],
+                [1, 2],
+                [1, 4],
+                [2, 5],
+                [3, 4],
+                [3, 5],
+                [4, 5],
+            ],
+            Self::Pyramid => &[
+                [0, 1],
+                [0, 3],
+                [0, 4],
+                [1, 2],
+                [1, 4],
+                [2, 3],
+                [2, 4],
+                [3, 4],
+            ],
+        }
+    }
+
+    /// Local vertices of each face, following the gmsh reference element.
+    ///
+    /// Faces of volume elements are oriented with outward normals; surface
+    /// elements have a single face, the element itself.
+    pub const fn faces(&self) -> &'static [&'static [usize]] {
+        match self {
+            Self::Point | Self::Line => &[],
+            Self::Triangle => &[&[0, 1, 2]],
+            Self::Quadrangle => &[&[0, 1, 2, 3]],
+            Self::Tetrahedron => &[&[0, 2, 1], &[0, 1, 3], &[0, 3, 2], &[3, 1, 2]],
+            Self::Hexahedron => &[
+                &[0, 3, 2, 1],
+                &[0, 1, 5, 4],
+                &[0, 4, 7, 3],
+                &[1, 2, 6, 5],
+                &[2, 3, 7, 6],
+                &[4, 5, 6, 7],
+            ],
+            Self::Prism => &[
+                &[0, 2, 1],
+                &[3, 4, 5],
+                &[0, 1, 4, 3],
+                &[0, 3, 5, 2],
+                &[1, 2, 5, 4],
+            ],
+            Self::Pyramid => &[
+                &[0, 1, 4],
+                &[3, 0, 4],
+                &[1, 2, 4],
+                &[2, 3, 4],
+                &[0, 3, 2, 1],
+            ],
+        }
+    }
+}
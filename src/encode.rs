@@ -0,0 +1,165 @@
+//! Writers of meshes in the formats of other tools.
+
+use {
+    crate::{
+        data::Data,
+        element::{self, Elementary, Family, Physical, Topology},
+        geometry::Point,
+        mesh::Mesh,
+        node::{self, Node},
+    },
+    std::collections::HashMap,
+};
+
+#[cfg(test)]
+mod test;
+
+pub(crate) mod abaqus;
+pub(crate) mod foam;
+pub(crate) mod medit;
+pub(crate) mod nastran;
+pub(crate) mod obj;
+pub(crate) mod off;
+pub(crate) mod ply;
+pub(crate) mod stl;
+pub(crate) mod su2;
+pub(crate) mod tetgen;
+pub(crate) mod vtk;
+pub(crate) mod vtu;
+pub(crate) mod xdmf;
+
+/// Vertices of a surface and its polygons, as indices into them.
+type SurfaceMesh = (Vec<Point>, Vec<(Physical, Vec<usize>)>);
+
+/// Faces written to surface formats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Surface {
+    /// Triangle and quadrangle elements, quadrangles split in two where a
+    /// format only holds triangles.
+    Elements,
+    /// Boundary faces of the volume elements, tagged with the physical tag of
+    /// the surface element covering them, or 0.
+    Boundary,
+}
+
+/// Nodes of `mesh` sorted by id.
+pub(crate) fn nodes(mesh: &Mesh) -> Vec<(node::Id, Node)> {
+    let mut nodes: Vec<(node::Id, Node)> = mesh.nodes.iter().map(|(&id, &n)| (id, n)).collect();
+    nodes.sort_by_key(|&(id, _)| id);
+    nodes
+}
+
+/// Zero-based position of each node id in `nodes`.
+pub(crate) fn indices(nodes: &[(node::Id, Node)]) -> HashMap<node::Id, usize> {
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(index, &(id, _))| (id, index))
+        .collect()
+}
+
+/// Error of an element over a node missing from the mesh.
+pub(crate) fn missing(id: node::Id) -> std::io::Error {
+    std::io::Error::other(format!(
+        "failed to encode mesh: element over missing node {id}"
+    ))
+}
+
+/// Coordinates of node `id`, failing if it is missing from `mesh`.
+pub(crate) fn point(mesh: &Mesh, id: node::Id) -> Result<Point, std::io::Error> {
+    mesh.nodes
+        .get(&id)
+        .map(Node::coordinates)
+        .ok_or_else(|| missing(id))
+}
+
+/// Zero-based positions in `indices` of the nodes of `topology`, failing on
+/// a node missing from the mesh.
+pub(crate) fn positions(
+    topology: &Topology,
+    indices: &HashMap<node::Id, usize>,
+) -> Result<Vec<usize>, std::io::Error> {
+    topology
+        .nodes()
+        .iter()
+        .map(|id| indices.get(id).copied().ok_or_else(|| missing(*id)))
+        .collect()
+}
+
+/// Name of the physical group `physical` of `dimension`, or `physical_<tag>`
+/// when it has none, without whitespace.
+pub(crate) fn name(mesh: &Mesh, dimension: i32, physical: Physical) -> String {
+    mesh.physical_names.get(&(dimension, physical)).map_or_else(
+        || format!("physical_{physical}"),
+        |name| name.replace(char::is_whitespace, "_"),
+    )
+}
+
+/// Elements of `mesh` sorted by id.
+pub(crate) fn elements(mesh: &Mesh) -> Vec<(element::Id, Physical, Elementary, Topology)> {
+    let mut elements: Vec<_> = mesh
+        .elements
+        .iter()
+        .map(|(&id, &(physical, elementary, topology))| (id, physical, elementary, topology))
+        .collect();
+    elements.sort_by_key(|&(id, ..)| id);
+    elements
+}
+
+/// Values of `data` for each of `ids` in turn, NaN where it has none.
+pub(crate) fn values<I: IntoIterator<Item = i32>>(data: &Data, ids: I) -> Vec<f64> {
+    let missing = vec![f64::NAN; data.components()];
+    ids.into_iter()
+        .flat_map(|id| data.get(id).unwrap_or(&missing).to_vec())
+        .collect()
+}
+
+impl Mesh {
+    /// Polygons of `surface` with their physical tags, in element order.
+    pub(crate) fn polygons(&self, surface: Surface) -> Vec<(Physical, Vec<node::Id>)> {
+        match surface {
+            Surface::Elements => elements(self)
+                .into_iter()
+                .filter(|(.., t)| matches!(t.family(), Family::Triangle | Family::Quadrangle))
+                .map(|(_, physical, _, topology)| (physical, topology.nodes()))
+                .collect(),
+            Surface::Boundary => self
+                .boundary()
+                .into_iter()
+                .filter(|facet| facet.topology().dimension() == 2)
+                .map(|facet| (facet.physical().unwrap_or(0), facet.topology().nodes()))
+                .collect(),
+        }
+    }
+
+    /// Vertices of the polygons of `surface`, sorted by node id, and the
+    /// polygons as zero-based indices into them.
+    pub(crate) fn surface(&self, surface: Surface) -> Result<SurfaceMesh, std::io::Error> {
+        let polygons = self.polygons(surface);
+        let mut ids: Vec<node::Id> = polygons.iter().flat_map(|(_, n)| n.clone()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        let indices: HashMap<node::Id, usize> =
+            ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        let points = ids
+            .iter()
+            .map(|&id| point(self, id))
+            .collect::<Result<_, _>>()?;
+        let polygons = polygons
+            .into_iter()
+            .map(|(physical, nodes)| (physical, nodes.iter().map(|id| indices[id]).collect()))
+            .collect();
+        Ok((points, polygons))
+    }
+
+    /// Triangles of `surface` with their physical tags, quadrangles split
+    /// along their first diagonal.
+    pub(crate) fn triangles(&self, surface: Surface) -> Vec<(Physical, [node::Id; 3])> {
+        self.polygons(surface)
+            .into_iter()
+            .flat_map(|(physical, nodes)| {
+                (1..nodes.len() - 1).map(move |i| (physical, [nodes[0], nodes[i], nodes[i + 1]]))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,148 @@
+//! Abaqus input files, `.inp`.
+
+use {
+    crate::{
+        element::{self, Family, Physical},
+        encode,
+        mesh::Mesh,
+        node,
+    },
+    std::{
+        collections::{BTreeMap, HashMap},
+        io::Write,
+    },
+};
+
+/// Abaqus element type of `family`: continuum volumes, shells and trusses,
+/// `None` for points and pyramids, which Abaqus lacks.
+pub(crate) const fn kind(family: Family) -> Option<&'static str> {
+    let kind = match family {
+        Family::Line => "T3D2",
+        Family::Triangle => "S3",
+        Family::Quadrangle => "S4",
+        Family::Tetrahedron => "C3D4",
+        Family::Hexahedron => "C3D8",
+        Family::Prism => "C3D6",
+        Family::Point | Family::Pyramid => return None,
+    };
+    Some(kind)
+}
+
+/// Abaqus face label of each face of `family`, `S<label>`, in the order of
+/// `Family::faces`.
+pub(crate) const fn labels(family: Family) -> &'static [usize] {
+    match family {
+        Family::Tetrahedron => &[1, 2, 4, 3],
+        Family::Hexahedron => &[1, 3, 6, 4, 5, 2],
+        Family::Prism => &[1, 2, 3, 5, 4],
+        _ => &[],
+    }
+}
+
+/// Writes `ids` sixteen to a line, the most Abaqus reads.
+fn list<W: Write>(writer: &mut W, ids: &[i32]) -> Result<(), std::io::Error> {
+    for line in ids.chunks(16) {
+        let line: Vec<String> = line.iter().map(i32::to_string).collect();
+        writeln!(writer, "{}", line.join(", "))?;
+    }
+    Ok(())
+}
+
+impl Mesh {
+    /// Writes the mesh as an Abaqus input file, with an element block per
+    /// element type and element and node sets per physical group, named after
+    /// the group or `physical_<dimension>_<tag>` when it has no name.
+    ///
+    /// In meshes with volume elements, surface elements are not written as
+    /// shells: each group of them becomes an element-based `*SURFACE` on the
+    /// faces of the volume elements they cover, which leaves out those covering
+    /// none. Elements tagged 0 belong to no set; points and pyramids are
+    /// skipped.
+    pub fn encode_abaqus<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        writeln!(writer, "*HEADING")?;
+        writeln!(writer, "rutile")?;
+
+        writeln!(writer, "*NODE")?;
+        for (id, node) in encode::nodes(self) {
+            writeln!(writer, "{}, {}, {}, {}", id, node.x(), node.y(), node.z())?;
+        }
+
+        let elements: Vec<_> = encode::elements(self)
+            .into_iter()
+            .filter(|(.., t)| kind(t.family()).is_some())
+            .collect();
+        let volume = elements.iter().any(|(.., t)| t.dimension() == 3);
+
+        // Owner and label of the faces of the volume elements, by their nodes.
+        let mut faces: HashMap<Vec<node::Id>, (element::Id, usize)> = HashMap::new();
+        for (id, .., topology) in &elements {
+            for (mut face, &label) in topology.faces().into_iter().zip(labels(topology.family())) {
+                face.sort_unstable();
+                faces.entry(face).or_insert((*id, label));
+            }
+        }
+
+        let mut blocks: BTreeMap<Family, Vec<(element::Id, Vec<node::Id>)>> = BTreeMap::new();
+        let mut sets: BTreeMap<(i32, Physical), Vec<element::Id>> = BTreeMap::new();
+        for (id, physical, _, topology) in &elements {
+            if !(volume && topology.dimension() == 2) {
+                blocks
+                    .entry(topology.family())
+                    .or_default()
+                    .push((*id, topology.nodes()));
+            }
+            if *physical != 0 {
+                sets.entry((topology.dimension(), *physical))
+                    .or_default()
+                    .push(*id);
+            }
+        }
+        for (family, block) in blocks {
+            if let Some(kind) = kind(family) {
+                writeln!(writer, "*ELEMENT, TYPE={kind}")?;
+            }
+            for (id, nodes) in block {
+                let mut line = vec![id];
+                line.extend(nodes);
+                list(writer, &line)?;
+            }
+        }
+
+        for ((dimension, physical), ids) in sets {
+            // Tags alone may repeat across dimensions, unlike set names.
+            let name = match self.physical_names.contains_key(&(dimension, physical)) {
+                true => encode::name(self, dimension, physical),
+                false => format!("physical_{dimension}_{physical}"),
+            };
+            if volume && dimension == 2 {
+                let covered: Vec<_> = ids
+                    .iter()
+                    .filter_map(|id| {
+                        let mut key = self.elements[id].2.nodes();
+                        key.sort_unstable();
+                        faces.get(&key)
+                    })
+                    .collect();
+                if !covered.is_empty() {
+                    writeln!(writer, "*SURFACE, TYPE=ELEMENT, NAME={name}")?;
+                }
+                for (owner, label) in covered {
+                    writeln!(writer, "{owner}, S{label}")?;
+                }
+            } else {
+                writeln!(writer, "*ELSET, ELSET={name}")?;
+                list(writer, &ids)?;
+            }
+
+            let mut nodes: Vec<node::Id> = ids
+                .iter()
+                .flat_map(|id| self.elements[id].2.nodes())
+                .collect();
+            nodes.sort_unstable();
+            nodes.dedup();
+            writeln!(writer, "*NSET, NSET={name}")?;
+            list(writer, &nodes)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+//! OpenFOAM `polyMesh` directories.
+
+use {
+    crate::{
+        element::{Physical, Topology},
+        encode,
+        mesh::Mesh,
+        node,
+    },
+    std::{
+        collections::{BTreeMap, HashMap},
+        fs::File,
+        io::{BufWriter, Write},
+        path::Path,
+    },
+};
+
+/// Face-based description of a mesh, as OpenFOAM stores it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Poly {
+    pub(crate) points: Vec<[f64; 3]>,
+    /// Internal faces first, then the faces of each patch in turn.
+    pub(crate) faces: Vec<Vec<usize>>,
+    pub(crate) owner: Vec<usize>,
+    pub(crate) neighbour: Vec<usize>,
+    /// Name, number of faces and first face of each patch.
+    pub(crate) patches: Vec<(String, usize, usize)>,
+    pub(crate) cells: usize,
+}
+
+/// Owning cell and point indices of a face.
+type Face = (usize, Vec<usize>);
+
+/// Patch of the boundary faces not covered by a tagged surface element.
+const DEFAULT: &str = "defaultFaces";
+
+impl Mesh {
+    pub(crate) fn poly(&self) -> Result<Poly, std::io::Error> {
+        let cells: Vec<Topology> = encode::elements(self)
+            .into_iter()
+            .filter(|(.., t)| t.dimension() == 3)
+            .map(|(.., t)| t)
+            .collect();
+
+        let mut used: Vec<node::Id> = cells.iter().flat_map(Topology::nodes).collect();
+        used.sort_unstable();
+        used.dedup();
+        let index: HashMap<node::Id, usize> =
+            used.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+        // Owner and nodes of each face, in the orientation of its first cell.
+        let mut faces: HashMap<Vec<node::Id>, (usize, Vec<usize>, Option<usize>)> = HashMap::new();
+        for (cell, topology) in cells.iter().enumerate() {
+            for face in topology.faces() {
+                let mut key = face.clone();
+                key.sort_unstable();
+                faces
+                    .entry(key)
+                    .and_modify(|(_, _, neighbour)| *neighbour = Some(cell))
+                    .or_insert_with(|| (cell, face.iter().map(|id| index[id]).collect(), None));
+            }
+        }
+
+        let mut patches: HashMap<Vec<node::Id>, Physical> = HashMap::new();
+        for (physical, _, topology) in self.elements.values() {
+            if topology.dimension() == 2 && *physical != 0 {
+                let mut key = topology.nodes();
+                key.sort_unstable();
+                patches.insert(key, *physical);
+            }
+        }
+
+        let mut internal = Vec::new();
+        // Keyed so that tagged patches sort before the default one.
+        let mut boundary: BTreeMap<(bool, Physical), Vec<Face>> = BTreeMap::new();
+        for (key, (owner, nodes, neighbour)) in faces {
+            match neighbour {
+                Some(neighbour) => internal.push((owner, neighbour, nodes)),
+                None => boundary
+                    .entry(patches.get(&key).map_or((true, 0), |&p| (false, p)))
+                    .or_default()
+                    .push((owner, nodes)),
+            }
+        }
+        // Upper triangular order: by owner, then by neighbour.
+        internal.sort();
+
+        let mut poly = Poly {
+            points: used
+                .iter()
+                .map(|&id| encode::point(self, id))
+                .collect::<Result<_, _>>()?,
+            cells: cells.len(),
+            ..Poly::default()
+        };
+        for (owner, neighbour, nodes) in internal {
+            poly.faces.push(nodes);
+            poly.owner.push(owner);
+            poly.neighbour.push(neighbour);
+        }
+        for ((default, physical), mut faces) in boundary {
+            faces.sort();
+            let name = match default {
+                false => encode::name(self, 2, physical),
+                true => String::from(DEFAULT),
+            };
+            poly.patches.push((name, faces.len(), poly.faces.len()));
+            for (owner, nodes) in faces {
+                poly.faces.push(nodes);
+                poly.owner.push(owner);
+            }
+        }
+        Ok(poly)
+    }
+
+    /// Writes the volume elements of the mesh as an ASCII OpenFOAM
+    /// `polyMesh` in `directory`, creating it if needed.
+    ///
+    /// Boundary faces covered by a surface element with a physical tag form a
+    /// patch named after the physical group, or `physical_<tag>`; the others
+    /// form the `defaultFaces` patch. Only nodes of volume elements are kept.
+    pub fn encode_foam<P: AsRef<Path>>(&self, directory: P) -> Result<(), std::io::Error> {
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory)?;
+        let poly = self.poly()?;
+        let note = format!(
+            "nPoints: {} nCells: {} nFaces: {} nInternalFaces: {}",
+            poly.points.len(),
+            poly.cells,
+            poly.faces.len(),
+            poly.neighbour.len()
+        );
+        let open = |name: &str| File::create(directory.join(name)).map(BufWriter::new);
+
+        let mut writer = open("points")?;
+        header(&mut writer, "vectorField", "points", None)?;
+        writeln!(writer, "{}\n(", poly.points.len())?;
+        for p in &poly.points {
+            writeln!(writer, "({} {} {})", p[0], p[1], p[2])?;
+        }
+        writeln!(writer, ")")?;
+        writer.flush()?;
+
+        let mut writer = open("faces")?;
+        header(&mut writer, "faceList", "faces", None)?;
+        writeln!(writer, "{}\n(", poly.faces.len())?;
+        for face in &poly.faces {
+            let face: Vec<String> = face.iter().map(usize::to_string).collect();
+            writeln!(writer, "{}({})", face.len(), face.join(" "))?;
+        }
+        writeln!(writer, ")")?;
+        writer.flush()?;
+
+        for (object, labels) in [("owner", &poly.owner), ("neighbour", &poly.neighbour)] {
+            let mut writer = open(object)?;
+            header(&mut writer, "labelList", object, Some(&note))?;
+            writeln!(writer, "{}\n(", labels.len())?;
+            for label in labels {
+                writeln!(writer, "{label}")?;
+            }
+            writeln!(writer, ")")?;
+            writer.flush()?;
+        }
+
+        let mut writer = open("boundary")?;
+        header(&mut writer, "polyBoundaryMesh", "boundary", None)?;
+        writeln!(writer, "{}\n(", poly.patches.len())?;
+        for (name, faces, start) in &poly.patches {
+            writeln!(writer, "    {name}\n    {{")?;
+            writeln!(writer, "        type            patch;")?;
+            writeln!(writer, "        nFaces          {faces};")?;
+            writeln!(writer, "        startFace       {start};")?;
+            writeln!(writer, "    }}")?;
+        }
+        writeln!(writer, ")")?;
+        writer.flush()
+    }
+}
+
+fn header<W: Write>(
+    writer: &mut W,
+    class: &str,
+    object: &str,
+    note: Option<&str>,
+) -> Result<(), std::io::Error> {
+    writeln!(writer, "FoamFile\n{{")?;
+    writeln!(writer, "    version     2.0;")?;
+    writeln!(writer, "    format      ascii;")?;
+    writeln!(writer, "    class       {class};")?;
+    if let Some(note) = note {
+        writeln!(writer, "    note        \"{note}\";")?;
+    }
+    writeln!(writer, "    location    \"constant/polyMesh\";")?;
+    writeln!(writer, "    object      {object};")?;
+    writeln!(writer, "}}\n")
+}
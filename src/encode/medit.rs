@@ -0,0 +1,118 @@
+//! INRIA MEDIT meshes, ASCII `.mesh` and binary `.meshb`.
+
+use {
+    crate::{element::Family, encode, mesh::Mesh},
+    std::io::Write,
+};
+
+/// MEDIT keyword of the elements of each family, with its binary code.
+pub(crate) const KEYWORDS: [(Family, &str, i32); 7] = [
+    (Family::Line, "Edges", 5),
+    (Family::Triangle, "Triangles", 6),
+    (Family::Quadrangle, "Quadrilaterals", 7),
+    (Family::Tetrahedron, "Tetrahedra", 8),
+    (Family::Prism, "Prisms", 9),
+    (Family::Hexahedron, "Hexahedra", 10),
+    (Family::Pyramid, "Pyramids", 49),
+];
+pub(crate) const DIMENSION: i32 = 3;
+pub(crate) const VERTICES: i32 = 4;
+pub(crate) const END: i32 = 54;
+
+/// Rows of the MEDIT sections: vertices with their coordinates, then the
+/// elements of each keyword as one-based vertex indices followed by their
+/// reference, the physical tag.
+type Sections = (Vec<[f64; 3]>, Vec<(usize, Vec<Vec<i64>>)>);
+
+fn sections(mesh: &Mesh) -> Result<Sections, std::io::Error> {
+    let nodes = encode::nodes(mesh);
+    let indices = encode::indices(&nodes);
+    let elements = encode::elements(mesh);
+    let blocks = KEYWORDS
+        .iter()
+        .enumerate()
+        .map(|(keyword, &(family, ..))| {
+            let rows = elements
+                .iter()
+                .filter(|(.., t)| t.family() == family)
+                .map(|(_, physical, _, topology)| {
+                    let positions = encode::positions(topology, &indices)?;
+                    let mut row: Vec<i64> = positions.iter().map(|&p| p as i64 + 1).collect();
+                    row.push(*physical as i64);
+                    Ok(row)
+                })
+                .collect::<Result<Vec<_>, std::io::Error>>()?;
+            Ok((keyword, rows))
+        })
+        .filter(|section| section.as_ref().map_or(true, |(_, rows)| !rows.is_empty()))
+        .collect::<Result<_, std::io::Error>>()?;
+    let points = nodes.iter().map(|(_, n)| n.coordinates()).collect();
+    Ok((points, blocks))
+}
+
+fn int(bytes: &mut Vec<u8>, value: i32) {
+    bytes.extend(value.to_le_bytes());
+}
+
+/// Appends keyword `code` with its `body`, preceded by the position of the
+/// next keyword.
+fn keyword(bytes: &mut Vec<u8>, code: i32, body: &[u8]) {
+    int(bytes, code);
+    let next = bytes.len() + 4 + body.len();
+    int(bytes, next as i32);
+    bytes.extend(body);
+}
+
+impl Mesh {
+    /// Writes the mesh as an ASCII MEDIT file, with physical tags as element
+    /// references and vertex references set to 0.
+    ///
+    /// Vertices are numbered from 1 in node id order; points are skipped.
+    pub fn encode_medit<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        let (points, blocks) = sections(self)?;
+        writeln!(writer, "MeshVersionFormatted 2")?;
+        writeln!(writer, "Dimension 3")?;
+        writeln!(writer, "Vertices")?;
+        writeln!(writer, "{}", points.len())?;
+        for p in &points {
+            writeln!(writer, "{} {} {} 0", p[0], p[1], p[2])?;
+        }
+        for (keyword, rows) in blocks {
+            writeln!(writer, "{}", KEYWORDS[keyword].1)?;
+            writeln!(writer, "{}", rows.len())?;
+            for row in rows {
+                let row: Vec<String> = row.iter().map(i64::to_string).collect();
+                writeln!(writer, "{}", row.join(" "))?;
+            }
+        }
+        writeln!(writer, "End")
+    }
+
+    /// Writes the mesh as a binary MEDIT file, version 2: little endian, with
+    /// double precision coordinates and 32 bit integers and positions.
+    pub fn encode_medit_binary<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        let (points, blocks) = sections(self)?;
+        let mut bytes: Vec<u8> = Vec::new();
+        int(&mut bytes, 1);
+        int(&mut bytes, 2);
+        keyword(&mut bytes, DIMENSION, &3i32.to_le_bytes());
+
+        let mut body = (points.len() as i32).to_le_bytes().to_vec();
+        for p in &points {
+            body.extend(p.iter().flat_map(|x| x.to_le_bytes()));
+            body.extend(0i32.to_le_bytes());
+        }
+        keyword(&mut bytes, VERTICES, &body);
+
+        for (index, rows) in blocks {
+            let mut body = (rows.len() as i32).to_le_bytes().to_vec();
+            for row in rows {
+                body.extend(row.iter().flat_map(|&v| (v as i32).to_le_bytes()));
+            }
+            keyword(&mut bytes, KEYWORDS[index].2, &body);
+        }
+        int(&mut bytes, END);
+        int(&mut bytes, 0);
+        writer.write_all(&bytes)
+    }
+}
@@ -0,0 +1,181 @@
+//! Nastran bulk data, `.bdf`.
+
+use {
+    crate::{
+        element::Family,
+        encode,
+        geometry::{self, Point},
+        mesh::Mesh,
+    },
+    std::io::Write,
+};
+
+/// Width of the fields of Nastran cards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Field {
+    /// Eight characters, eight data fields per line.
+    Small,
+    /// Sixteen characters, four data fields per line.
+    Large,
+}
+
+impl Field {
+    const fn width(&self) -> usize {
+        match self {
+            Self::Small => 8,
+            Self::Large => 16,
+        }
+    }
+
+    const fn fields(&self) -> usize {
+        match self {
+            Self::Small => 8,
+            Self::Large => 4,
+        }
+    }
+}
+
+/// Nastran card of the elements of `family`, `None` for points.
+const fn card(family: Family) -> Option<&'static str> {
+    let card = match family {
+        Family::Line => "CBAR",
+        Family::Triangle => "CTRIA3",
+        Family::Quadrangle => "CQUAD4",
+        Family::Tetrahedron => "CTETRA",
+        Family::Hexahedron => "CHEXA",
+        Family::Prism => "CPENTA",
+        Family::Pyramid => "CPYRAM",
+        Family::Point => return None,
+    };
+    Some(card)
+}
+
+/// `x` in at most `width` characters with a decimal point, in fixed or
+/// exponent notation, whichever is closer; the exponent drops its `E` as
+/// Nastran allows.
+fn real(x: f64, width: usize) -> String {
+    if x == 0.0 {
+        return String::from("0.");
+    }
+    let fixed = (0..width)
+        .rev()
+        .map(|precision| {
+            let ss = format!("{x:.precision$}");
+            match ss.contains('.') {
+                true => ss.trim_end_matches('0').to_string(),
+                false => format!("{ss}."),
+            }
+        })
+        .find(|ss| ss.len() <= width);
+    let exponent = (0..width)
+        .rev()
+        .map(|precision| {
+            let ss = format!("{x:.precision$e}");
+            let (mantissa, exponent) = ss.split_once('e').unwrap_or((&ss, "0"));
+            let mantissa = match mantissa.contains('.') {
+                true => mantissa.trim_end_matches('0').to_string(),
+                false => format!("{mantissa}."),
+            };
+            match exponent.starts_with('-') {
+                true => format!("{mantissa}{exponent}"),
+                false => format!("{mantissa}+{exponent}"),
+            }
+        })
+        .find(|ss| ss.len() <= width);
+    let error = |ss: &String| {
+        let value: f64 = match ss[1..].find(['+', '-']).map(|i| i + 1) {
+            Some(i) => format!("{}e{}", &ss[..i], &ss[i..]).parse(),
+            None => ss.parse(),
+        }
+        .unwrap_or(f64::INFINITY);
+        (value - x).abs()
+    };
+    [fixed, exponent]
+        .into_iter()
+        .flatten()
+        .min_by(|a, b| error(a).total_cmp(&error(b)))
+        .unwrap_or_default()
+}
+
+/// Orientation vector of a bar along `direction`: the coordinate axis most
+/// orthogonal to it.
+fn orientation(direction: Point) -> Point {
+    let axis = (0..3)
+        .min_by(|&a, &b| direction[a].abs().total_cmp(&direction[b].abs()))
+        .unwrap_or(0);
+    let mut v = [0.0; 3];
+    v[axis] = 1.0;
+    v
+}
+
+/// Writes a card over as many lines as its fields need.
+fn write<W: Write>(
+    writer: &mut W,
+    field: Field,
+    name: &str,
+    fields: &[String],
+) -> Result<(), std::io::Error> {
+    let width = field.width();
+    let (name, continuation) = match field {
+        Field::Small => (name.to_string(), "+"),
+        Field::Large => (format!("{name}*"), "*"),
+    };
+    for (index, line) in fields.chunks(field.fields()).enumerate() {
+        let first = if index == 0 { &name } else { continuation };
+        write!(writer, "{first:<8}")?;
+        for value in line {
+            write!(writer, "{value:<width$}")?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+impl Mesh {
+    /// Writes the mesh as Nastran bulk data: `GRID` cards for the nodes and
+    /// element cards with their physical tag as `PID`. Property ids must be
+    /// positive, so elements tagged 0 get `PID` 1, shared with those tagged
+    /// 1. Property cards are left to the user.
+    ///
+    /// Bars are oriented along the coordinate axis most orthogonal to them;
+    /// points are skipped.
+    pub fn encode_nastran<W: Write>(
+        &self,
+        writer: &mut W,
+        field: Field,
+    ) -> Result<(), std::io::Error> {
+        let width = field.width();
+        writeln!(writer, "$ rutile")?;
+        writeln!(writer, "BEGIN BULK")?;
+        for (id, node) in encode::nodes(self) {
+            let mut fields = vec![id.to_string(), String::new()];
+            fields.extend(node.coordinates().map(|x| real(x, width)));
+            write(writer, field, "GRID", &fields)?;
+        }
+        for (id, physical, _, topology) in encode::elements(self) {
+            let Some(name) = card(topology.family()) else {
+                continue;
+            };
+            let pid = match physical {
+                0 => 1,
+                physical => physical,
+            };
+            let mut fields = vec![id.to_string(), pid.to_string()];
+            let nodes = topology.nodes();
+            let points = nodes
+                .iter()
+                .map(|&id| encode::point(self, id))
+                .collect::<Result<Vec<_>, _>>()?;
+            fields.extend(nodes.iter().map(i32::to_string));
+            if topology.family() == Family::Line {
+                let direction = match points[..] {
+                    [a, b] => geometry::sub(b, a),
+                    _ => [1.0, 0.0, 0.0],
+                };
+                fields.extend(orientation(direction).map(|x| real(x, width)));
+            }
+            write(writer, field, name, &fields)?;
+        }
+        writeln!(writer, "ENDDATA")
+    }
+}
@@ -0,0 +1,39 @@
+//! Wavefront OBJ surfaces.
+
+use {
+    crate::{
+        element::Physical,
+        encode::{self, Surface},
+        mesh::Mesh,
+    },
+    std::io::Write,
+};
+
+impl Mesh {
+    /// Writes the triangles and quadrangles of `surface` as a Wavefront OBJ
+    /// file, with a group per run of faces sharing a physical tag, named
+    /// after the physical group.
+    ///
+    /// Only the nodes of the faces are written, numbered from 1 in id order.
+    pub fn encode_obj<W: Write>(
+        &self,
+        writer: &mut W,
+        surface: Surface,
+    ) -> Result<(), std::io::Error> {
+        let (points, polygons) = self.surface(surface)?;
+        for p in &points {
+            writeln!(writer, "v {} {} {}", p[0], p[1], p[2])?;
+        }
+        let mut group: Option<Physical> = None;
+        for (physical, vertices) in &polygons {
+            if group != Some(*physical) {
+                let name = encode::name(self, 2, *physical);
+                writeln!(writer, "g {name}")?;
+                group = Some(*physical);
+            }
+            let vertices: Vec<String> = vertices.iter().map(|v| (v + 1).to_string()).collect();
+            writeln!(writer, "f {}", vertices.join(" "))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+//! Object File Format surfaces.
+
+use {
+    crate::{encode::Surface, mesh::Mesh},
+    std::io::Write,
+};
+
+impl Mesh {
+    /// Writes the triangles and quadrangles of `surface` as an OFF file.
+    /// Physical tags are dropped.
+    ///
+    /// Only the nodes of the faces are written, numbered from 0 in id order.
+    pub fn encode_off<W: Write>(
+        &self,
+        writer: &mut W,
+        surface: Surface,
+    ) -> Result<(), std::io::Error> {
+        let (points, polygons) = self.surface(surface)?;
+        writeln!(writer, "OFF")?;
+        writeln!(writer, "{} {} 0", points.len(), polygons.len())?;
+        for p in &points {
+            writeln!(writer, "{} {} {}", p[0], p[1], p[2])?;
+        }
+        for (_, vertices) in &polygons {
+            let vertices: Vec<String> = vertices.iter().map(usize::to_string).collect();
+            writeln!(writer, "{} {}", vertices.len(), vertices.join(" "))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+//! Stanford PLY surfaces, in ASCII or binary.
+
+use {
+    crate::{encode::Surface, mesh::Mesh},
+    std::io::Write,
+};
+
+/// Writes the header of a PLY file in `format`.
+fn header<W: Write>(
+    writer: &mut W,
+    format: &str,
+    vertices: usize,
+    faces: usize,
+) -> Result<(), std::io::Error> {
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format {format} 1.0")?;
+    writeln!(writer, "comment rutile")?;
+    writeln!(writer, "element vertex {vertices}")?;
+    for x in ["x", "y", "z"] {
+        writeln!(writer, "property double {x}")?;
+    }
+    writeln!(writer, "element face {faces}")?;
+    writeln!(writer, "property list uchar int vertex_indices")?;
+    writeln!(writer, "property int physical")?;
+    writeln!(writer, "end_header")
+}
+
+impl Mesh {
+    /// Writes the triangles and quadrangles of `surface` as an ASCII PLY
+    /// file, with the physical tag of each face as its `physical` property.
+    ///
+    /// Only the nodes of the faces are written, numbered from 0 in id order.
+    pub fn encode_ply<W: Write>(
+        &self,
+        writer: &mut W,
+        surface: Surface,
+    ) -> Result<(), std::io::Error> {
+        let (points, polygons) = self.surface(surface)?;
+        header(writer, "ascii", points.len(), polygons.len())?;
+        for p in &points {
+            writeln!(writer, "{} {} {}", p[0], p[1], p[2])?;
+        }
+        for (physical, vertices) in &polygons {
+            let vertices: Vec<String> = vertices.iter().map(usize::to_string).collect();
+            writeln!(
+                writer,
+                "{} {} {}",
+                vertices.len(),
+                vertices.join(" "),
+                physical
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes the triangles and quadrangles of `surface` as a little endian
+    /// binary PLY file, laid out as by [`Mesh::encode_ply`].
+    pub fn encode_ply_binary<W: Write>(
+        &self,
+        writer: &mut W,
+        surface: Surface,
+    ) -> Result<(), std::io::Error> {
+        let (points, polygons) = self.surface(surface)?;
+        header(writer, "binary_little_endian", points.len(), polygons.len())?;
+        for p in &points {
+            for x in p {
+                writer.write_all(&x.to_le_bytes())?;
+            }
+        }
+        for (physical, vertices) in &polygons {
+            writer.write_all(&[vertices.len() as u8])?;
+            for &v in vertices {
+                writer.write_all(&(v as i32).to_le_bytes())?;
+            }
+            writer.write_all(&physical.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}
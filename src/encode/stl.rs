@@ -0,0 +1,113 @@
+//! STL triangulated surfaces, in ASCII or binary.
+
+use {
+    crate::{
+        element::Physical,
+        encode::{self, Surface},
+        geometry::{self, Point},
+        mesh::Mesh,
+    },
+    std::io::Write,
+};
+
+/// Triangle with its physical tag, normal and vertices.
+type Facet = (Physical, Point, [Point; 3]);
+
+impl Mesh {
+    fn facets(&self, surface: Surface) -> Result<Vec<Facet>, std::io::Error> {
+        self.triangles(surface)
+            .into_iter()
+            .map(|(physical, nodes)| {
+                let points = [
+                    encode::point(self, nodes[0])?,
+                    encode::point(self, nodes[1])?,
+                    encode::point(self, nodes[2])?,
+                ];
+                let normal = geometry::cross(
+                    geometry::sub(points[1], points[0]),
+                    geometry::sub(points[2], points[0]),
+                );
+                let length = geometry::norm(normal);
+                let normal = match length > 0.0 {
+                    true => geometry::scale(normal, 1.0 / length),
+                    false => [0.0; 3],
+                };
+                Ok((physical, normal, points))
+            })
+            .collect()
+    }
+
+    /// Writes the triangles of `surface` as an ASCII STL file, with a solid
+    /// per physical tag if `solids` is set, named after the physical group.
+    /// A surface without triangles is written as a single empty solid.
+    pub fn encode_stl<W: Write>(
+        &self,
+        writer: &mut W,
+        surface: Surface,
+        solids: bool,
+    ) -> Result<(), std::io::Error> {
+        let facets = self.facets(surface)?;
+        let mut groups: Vec<Option<Physical>> = match solids {
+            true => facets
+                .iter()
+                .map(|&(physical, ..)| Some(physical))
+                .collect(),
+            false => vec![None],
+        };
+        groups.sort_unstable();
+        groups.dedup();
+        if groups.is_empty() {
+            groups.push(None);
+        }
+
+        for group in groups {
+            let name = match group {
+                Some(physical) => encode::name(self, 2, physical),
+                None => String::from("rutile"),
+            };
+            writeln!(writer, "solid {name}")?;
+            let members = facets
+                .iter()
+                .filter(|(physical, ..)| group.is_none_or(|g| g == *physical));
+            for (_, normal, points) in members {
+                writeln!(
+                    writer,
+                    "  facet normal {} {} {}",
+                    normal[0], normal[1], normal[2]
+                )?;
+                writeln!(writer, "    outer loop")?;
+                for p in points {
+                    writeln!(writer, "      vertex {} {} {}", p[0], p[1], p[2])?;
+                }
+                writeln!(writer, "    endloop")?;
+                writeln!(writer, "  endfacet")?;
+            }
+            writeln!(writer, "endsolid {name}")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the triangles of `surface` as a binary STL file, with the
+    /// physical tag of each triangle as its attribute.
+    pub fn encode_stl_binary<W: Write>(
+        &self,
+        writer: &mut W,
+        surface: Surface,
+    ) -> Result<(), std::io::Error> {
+        let facets = self.facets(surface)?;
+        let mut header = [b' '; 80];
+        header[..6].copy_from_slice(b"rutile");
+        writer.write_all(&header)?;
+        writer.write_all(&(facets.len() as u32).to_le_bytes())?;
+        for (physical, normal, points) in facets {
+            for p in [normal].iter().chain(&points) {
+                for x in p {
+                    writer.write_all(&(*x as f32).to_le_bytes())?;
+                }
+            }
+            let attribute = physical.clamp(0, u16::MAX as i32) as u16;
+            writer.write_all(&attribute.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}
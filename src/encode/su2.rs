@@ -0,0 +1,84 @@
+//! SU2 native meshes, `.su2`.
+
+use {
+    crate::{
+        element::{self, Physical, Topology},
+        encode::{self, vtk},
+        mesh::Mesh,
+        node,
+    },
+    std::{
+        collections::{BTreeMap, HashMap},
+        io::Write,
+    },
+};
+
+fn row<W: Write>(
+    writer: &mut W,
+    topology: &Topology,
+    indices: &HashMap<node::Id, usize>,
+) -> Result<(), std::io::Error> {
+    write!(writer, "{}", vtk::cell(topology.family()))?;
+    for position in encode::positions(topology, indices)? {
+        write!(writer, " {position}")?;
+    }
+    Ok(())
+}
+
+impl Mesh {
+    /// Writes the mesh as an ASCII SU2 file: the elements of the highest
+    /// dimension as the volume, and those one dimension below as markers,
+    /// one per physical tag, named after the physical group or
+    /// `physical_<tag>`.
+    ///
+    /// Points are numbered from 0 in node id order. Untagged boundary
+    /// elements and the tags of the volume elements are dropped.
+    pub fn encode_su2<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        let nodes = encode::nodes(self);
+        let indices = encode::indices(&nodes);
+        let elements = encode::elements(self);
+        let dimension = elements
+            .iter()
+            .map(|(.., t)| t.dimension())
+            .max()
+            .unwrap_or(3)
+            .max(2);
+
+        writeln!(writer, "NDIME= {dimension}")?;
+        let volume: Vec<_> = elements
+            .iter()
+            .filter(|(.., t)| t.dimension() == dimension)
+            .collect();
+        writeln!(writer, "NELEM= {}", volume.len())?;
+        for (index, (.., topology)) in volume.iter().enumerate() {
+            row(writer, topology, &indices)?;
+            writeln!(writer, " {index}")?;
+        }
+
+        writeln!(writer, "NPOIN= {}", nodes.len())?;
+        for (index, (_, node)) in nodes.iter().enumerate() {
+            let coordinates = &node.coordinates()[..dimension as usize];
+            let coordinates: Vec<String> = coordinates.iter().map(f64::to_string).collect();
+            writeln!(writer, "{} {index}", coordinates.join(" "))?;
+        }
+
+        let mut markers: BTreeMap<Physical, Vec<element::Id>> = BTreeMap::new();
+        for (id, physical, _, topology) in &elements {
+            if topology.dimension() == dimension - 1 && *physical != 0 {
+                markers.entry(*physical).or_default().push(*id);
+            }
+        }
+        writeln!(writer, "NMARK= {}", markers.len())?;
+        for (physical, ids) in markers {
+            let name = encode::name(self, dimension - 1, physical);
+            writeln!(writer, "MARKER_TAG= {name}")?;
+            writeln!(writer, "MARKER_ELEMS= {}", ids.len())?;
+            for id in ids {
+                let (_, _, topology) = &self.elements[&id];
+                row(writer, topology, &indices)?;
+                writeln!(writer)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,575 @@
+use crate::{
+    base64,
+    data::Data,
+    element::Topology,
+    encode::{nastran::Field, vtu::Encoding, Surface},
+    mesh::{self, Mesh},
+    node::Node,
+};
+
+// Unit square split into two triangles, with an edge on its boundary.
+fn square() -> Mesh {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(1.0, 1.0, 0.0));
+    ns.insert(5, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 10, Topology::Triangle3(1, 2, 3)));
+    es.insert(2, (1, 10, Topology::Triangle3(1, 3, 5)));
+    es.insert(7, (2, 20, Topology::Line2(1, 2)));
+    Mesh::new(None, ns, es)
+}
+
+fn temperature() -> Data {
+    let mut data = Data::new("temperature", 1);
+    for (id, value) in [(1, 1.0), (2, 2.0), (3, 3.0)] {
+        data.insert(id, vec![value]).unwrap();
+    }
+    data
+}
+
+#[test]
+fn base64() {
+    assert_eq!(base64::encode(b""), "");
+    assert_eq!(base64::encode(b"f"), "Zg==");
+    assert_eq!(base64::encode(b"fo"), "Zm8=");
+    assert_eq!(base64::encode(b"foo"), "Zm9v");
+    assert_eq!(base64::encode(b"foobar"), "Zm9vYmFy");
+}
+
+#[test]
+fn vtk() {
+    let mut bytes = Vec::new();
+    square()
+        .encode_vtk(&mut bytes, &[temperature()], &[])
+        .unwrap();
+
+    let expected = "\
+# vtk DataFile Version 2.0
+rutile
+ASCII
+DATASET UNSTRUCTURED_GRID
+POINTS 4 double
+0 0 0
+1 0 0
+1 1 0
+0 1 0
+CELLS 3 11
+3 0 1 2
+3 0 2 3
+2 0 1
+CELL_TYPES 3
+5
+5
+3
+CELL_DATA 3
+FIELD FieldData 2
+gmsh:physical 1 3 int
+1
+1
+2
+gmsh:geometrical 1 3 int
+10
+10
+20
+POINT_DATA 4
+FIELD FieldData 1
+temperature 1 4 double
+1
+2
+3
+NaN
+";
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+}
+
+#[test]
+fn vtu() {
+    let mesh = square();
+    let mut bytes = Vec::new();
+    mesh.encode_vtu(&mut bytes, &[temperature()], &[], Encoding::Ascii)
+        .unwrap();
+    let ascii = String::from_utf8(bytes).unwrap();
+    assert!(ascii.contains("<Piece NumberOfPoints=\"4\" NumberOfCells=\"3\">"));
+    assert!(ascii.contains(
+        "<DataArray type=\"Int64\" Name=\"connectivity\" NumberOfComponents=\"1\" format=\"ascii\">\n0 1 2 0 2 3 0 1\n"
+    ));
+    assert!(ascii.contains("Name=\"offsets\" NumberOfComponents=\"1\" format=\"ascii\">\n3 6 8\n"));
+    assert!(ascii.contains("Name=\"types\" NumberOfComponents=\"1\" format=\"ascii\">\n5 5 3\n"));
+    assert!(ascii
+        .contains("Name=\"gmsh:physical\" NumberOfComponents=\"1\" format=\"ascii\">\n1 1 2\n"));
+    assert!(ascii
+        .contains("Name=\"temperature\" NumberOfComponents=\"1\" format=\"ascii\">\n1 2 3 NaN\n"));
+
+    let mut bytes = Vec::new();
+    mesh.encode_vtu(&mut bytes, &[], &[], Encoding::Base64)
+        .unwrap();
+    let binary = String::from_utf8(bytes).unwrap();
+    // Three UInt8 cell types after their UInt64 byte count.
+    let types = base64::encode(&[3, 0, 0, 0, 0, 0, 0, 0, 5, 5, 3]);
+    assert!(binary.contains(&format!("format=\"binary\">\n{types}\n")));
+
+    let mut bytes = Vec::new();
+    mesh.encode_vtu(&mut bytes, &[], &[], Encoding::Raw)
+        .unwrap();
+    let marker = b"<AppendedData encoding=\"raw\">\n_";
+    let start = bytes
+        .windows(marker.len())
+        .position(|w| w == marker)
+        .unwrap()
+        + marker.len();
+    // The physical tags come first.
+    let header = u64::from_le_bytes(bytes[start..start + 8].try_into().unwrap());
+    assert_eq!(header, 12);
+    let first = i32::from_le_bytes(bytes[start + 8..start + 12].try_into().unwrap());
+    assert_eq!(first, 1);
+    let text = String::from_utf8_lossy(&bytes);
+    assert!(text.contains(
+        "Name=\"gmsh:geometrical\" NumberOfComponents=\"1\" format=\"appended\" offset=\"20\">"
+    ));
+}
+
+#[test]
+fn stl() {
+    let mut mesh = square();
+    mesh.elements
+        .insert(3, (3, 30, Topology::Quadrangle4(1, 2, 3, 5)));
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((2, 3), String::from("the\tlid"));
+    let mesh = mesh.with_physical_names(names);
+
+    let mut bytes = Vec::new();
+    mesh.encode_stl(&mut bytes, Surface::Elements, true)
+        .unwrap();
+    let ss = String::from_utf8(bytes).unwrap();
+    let solids: Vec<&str> = ss.lines().filter(|l| l.starts_with("solid")).collect();
+    assert_eq!(solids, ["solid physical_1", "solid the_lid"]);
+    assert_eq!(ss.matches("facet normal 0 0 1").count(), 4);
+    assert!(ss.contains(
+        "solid the_lid\n  facet normal 0 0 1\n    outer loop\n      vertex 0 0 0\n      vertex 1 0 0\n      vertex 1 1 0\n    endloop\n  endfacet\n"
+    ));
+
+    let mut bytes = Vec::new();
+    mesh.encode_stl_binary(&mut bytes, Surface::Elements)
+        .unwrap();
+    assert_eq!(bytes.len(), 84 + 4 * 50);
+    assert_eq!(&bytes[80..84], &4u32.to_le_bytes());
+    assert_eq!(&bytes[84 + 3 * 50 + 48..], &3u16.to_le_bytes());
+
+    // A surface without triangles is still a solid.
+    let mut bytes = Vec::new();
+    mesh.encode_stl(&mut bytes, Surface::Boundary, true)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(bytes).unwrap(),
+        "solid rutile\nendsolid rutile\n"
+    );
+}
+
+#[test]
+fn boundary() {
+    // Unit tetrahedron with its bottom face tagged.
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(0.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 0.0, 1.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Tetrahedron4(1, 2, 3, 4)));
+    es.insert(2, (5, 2, Topology::Triangle3(1, 3, 2)));
+    let mesh = Mesh::new(None, ns, es);
+
+    let triangles = mesh.triangles(Surface::Boundary);
+    assert_eq!(triangles.len(), 4);
+    assert_eq!(triangles[0], (5, [1, 3, 2]));
+    assert!(triangles[1..].iter().all(|&(physical, _)| physical == 0));
+    assert_eq!(mesh.triangles(Surface::Elements), vec![(5, [1, 3, 2])]);
+}
+
+#[test]
+fn abaqus() {
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((2, 1), String::from("plate surface"));
+    let mesh = square().with_physical_names(names);
+
+    let mut bytes = Vec::new();
+    mesh.encode_abaqus(&mut bytes).unwrap();
+    let expected = "\
+*HEADING
+rutile
+*NODE
+1, 0, 0, 0
+2, 1, 0, 0
+3, 1, 1, 0
+5, 0, 1, 0
+*ELEMENT, TYPE=T3D2
+7, 1, 2
+*ELEMENT, TYPE=S3
+1, 1, 2, 3
+2, 1, 3, 5
+*ELSET, ELSET=physical_1_2
+7
+*NSET, NSET=physical_1_2
+1, 2
+*ELSET, ELSET=plate_surface
+1, 2
+*NSET, NSET=plate_surface
+1, 2, 3, 5
+";
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+
+    // Faces of volumes are surfaces rather than shells, and uncovered faces
+    // are dropped.
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(0.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 0.0, 1.0));
+    ns.insert(5, Node::new(1.0, 1.0, 1.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Tetrahedron4(1, 2, 3, 4)));
+    es.insert(2, (2, 2, Topology::Triangle3(2, 3, 4)));
+    es.insert(3, (2, 2, Topology::Triangle3(1, 2, 3)));
+    es.insert(4, (3, 3, Topology::Triangle3(2, 3, 5)));
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((2, 2), String::from("side"));
+    let mesh = Mesh::new(None, ns, es).with_physical_names(names);
+
+    let mut bytes = Vec::new();
+    mesh.encode_abaqus(&mut bytes).unwrap();
+    let expected = "\
+*HEADING
+rutile
+*NODE
+1, 0, 0, 0
+2, 1, 0, 0
+3, 0, 1, 0
+4, 0, 0, 1
+5, 1, 1, 1
+*ELEMENT, TYPE=C3D4
+1, 1, 2, 3, 4
+*SURFACE, TYPE=ELEMENT, NAME=side
+1, S3
+1, S1
+*NSET, NSET=side
+1, 2, 3, 4
+*NSET, NSET=physical_2_3
+2, 3, 5
+*ELSET, ELSET=physical_3_1
+1
+*NSET, NSET=physical_3_1
+1, 2, 3, 4
+";
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+}
+
+#[test]
+fn medit() {
+    let mut bytes = Vec::new();
+    square().encode_medit(&mut bytes).unwrap();
+    let expected = "\
+MeshVersionFormatted 2
+Dimension 3
+Vertices
+4
+0 0 0 0
+1 0 0 0
+1 1 0 0
+0 1 0 0
+Edges
+1
+1 2 2
+Triangles
+2
+1 2 3 1
+1 3 4 1
+End
+";
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+
+    let mut bytes = Vec::new();
+    square().encode_medit_binary(&mut bytes).unwrap();
+    let ints = |range: std::ops::Range<usize>| -> Vec<i32> {
+        bytes[range]
+            .chunks_exact(4)
+            .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect()
+    };
+    // Version, then the dimension keyword and the start of the vertices.
+    assert_eq!(ints(0..28), [1, 2, 3, 20, 3, 4, 32 + 4 * 28]);
+    assert_eq!(ints(bytes.len() - 8..bytes.len()), [54, 0]);
+}
+
+#[test]
+fn nastran() {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(10.0, 0.1, -2.0));
+    ns.insert(3, Node::new(1.5e-7, 123456.789, 0.0));
+    ns.insert(4, Node::new(0.0, 0.0, 1.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (3, 1, Topology::Tetrahedron4(1, 2, 3, 4)));
+    es.insert(2, (0, 1, Topology::Line2(1, 2)));
+    es.insert(3, (1, 1, Topology::Point1(1)));
+    let mesh = Mesh::new(None, ns, es);
+
+    let mut bytes = Vec::new();
+    mesh.encode_nastran(&mut bytes, Field::Small).unwrap();
+    let expected = "\
+$ rutile
+BEGIN BULK
+GRID    1               0.      0.      0.      
+GRID    2               10.     0.1     -2.     
+GRID    3               1.5-7   123456.80.      
+GRID    4               0.      0.      1.      
+CTETRA  1       3       1       2       3       4       
+CBAR    2       1       1       2       0.      1.      0.      
+ENDDATA
+";
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+
+    let mut bytes = Vec::new();
+    mesh.encode_nastran(&mut bytes, Field::Large).unwrap();
+    let ss = String::from_utf8(bytes).unwrap();
+    let lines: Vec<&str> = ss.lines().collect();
+    assert_eq!(
+        lines[6],
+        "GRID*   3                               0.00000015      123456.789      "
+    );
+    assert_eq!(lines[7], "*       0.              ");
+    assert_eq!(
+        lines[12],
+        "CBAR*   2               1               1               2               "
+    );
+}
+
+#[test]
+fn foam() {
+    // Two unit cubes along x, with the face x = 0 tagged.
+    let mut ns = mesh::Nodes::new();
+    let mut id = 0;
+    for x in 0..3 {
+        for (y, z) in [(0, 0), (1, 0), (1, 1), (0, 1)] {
+            id += 1;
+            ns.insert(id, Node::new(x as f64, y as f64, z as f64));
+        }
+    }
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Hexahedron8(1, 5, 6, 2, 4, 8, 7, 3)));
+    es.insert(2, (1, 1, Topology::Hexahedron8(5, 9, 10, 6, 8, 12, 11, 7)));
+    es.insert(3, (4, 2, Topology::Quadrangle4(1, 2, 3, 4)));
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((2, 4), String::from("inlet"));
+    let mesh = Mesh::new(None, ns, es).with_physical_names(names);
+
+    let poly = mesh.poly().unwrap();
+    assert_eq!(poly.points.len(), 12);
+    assert_eq!(poly.cells, 2);
+    assert_eq!(poly.faces.len(), 11);
+    assert_eq!(poly.neighbour, [1]);
+    assert_eq!(poly.owner[..2], [0, 0]);
+    assert_eq!(poly.owner.iter().filter(|&&o| o == 1).count(), 5);
+    assert_eq!(
+        poly.patches,
+        [
+            (String::from("inlet"), 1, 1),
+            (String::from("defaultFaces"), 9, 2),
+        ]
+    );
+
+    // The internal face x = 1 points out of its owner, towards +x.
+    let points: Vec<[f64; 3]> = poly.faces[0].iter().map(|&i| poly.points[i]).collect();
+    let normal = crate::geometry::newell(&points);
+    assert!(normal[0] > 0.0 && points.iter().all(|p| p[0] == 1.0));
+    // The inlet points out of the domain, towards -x.
+    let points: Vec<[f64; 3]> = poly.faces[1].iter().map(|&i| poly.points[i]).collect();
+    assert!(crate::geometry::newell(&points)[0] < 0.0);
+
+    let directory = std::env::temp_dir().join(format!("rutile-foam-{}", std::process::id()));
+    mesh.encode_foam(&directory).unwrap();
+    let boundary = std::fs::read_to_string(directory.join("boundary")).unwrap();
+    assert!(boundary.contains("    inlet\n    {\n        type            patch;\n        nFaces          1;\n        startFace       1;\n    }\n"));
+    let owner = std::fs::read_to_string(directory.join("owner")).unwrap();
+    assert!(owner.contains("note        \"nPoints: 12 nCells: 2 nFaces: 11 nInternalFaces: 1\";"));
+    assert!(owner.ends_with("11\n(\n0\n0\n0\n0\n0\n0\n1\n1\n1\n1\n1\n)\n"));
+    let faces = std::fs::read_to_string(directory.join("faces")).unwrap();
+    assert!(faces.contains("11\n(\n4("));
+    assert!(directory.join("points").exists() && directory.join("neighbour").exists());
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn su2() {
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((1, 2), String::from("bottom wall"));
+    let mesh = square().with_physical_names(names);
+
+    let mut bytes = Vec::new();
+    mesh.encode_su2(&mut bytes).unwrap();
+    let expected = "\
+NDIME= 2
+NELEM= 2
+5 0 1 2 0
+5 0 2 3 1
+NPOIN= 4
+0 0 0
+1 0 1
+1 1 2
+0 1 3
+NMARK= 1
+MARKER_TAG= bottom_wall
+MARKER_ELEMS= 1
+3 0 1
+";
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+}
+
+#[test]
+fn poly() {
+    let mut bytes = Vec::new();
+    square().encode_poly(&mut bytes).unwrap();
+    let expected = "\
+4 2 0 0
+1 0 0
+2 1 0
+3 1 1
+4 0 1
+1 1
+1 1 2 2
+0
+1
+1 0.6666666666666666 0.3333333333333333 1 -1
+";
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+}
+
+#[test]
+fn polygons() {
+    // Quadrangles are kept whole, and nodes off the surface dropped.
+    let mut mesh = square();
+    mesh.elements
+        .insert(3, (3, 30, Topology::Quadrangle4(1, 2, 3, 5)));
+    mesh.nodes.insert(4, Node::new(2.0, 2.0, 2.0));
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((2, 3), String::from("top\tlid"));
+    let mesh = mesh.with_physical_names(names);
+
+    let mut bytes = Vec::new();
+    mesh.encode_obj(&mut bytes, Surface::Elements).unwrap();
+    let expected = "\
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+g physical_1
+f 1 2 3
+f 1 3 4
+g top_lid
+f 1 2 3 4
+";
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+
+    let mut bytes = Vec::new();
+    mesh.encode_off(&mut bytes, Surface::Elements).unwrap();
+    let ss = String::from_utf8(bytes).unwrap();
+    assert!(ss.starts_with("OFF\n4 3 0\n0 0 0\n"));
+    assert!(ss.ends_with("3 0 2 3\n4 0 1 2 3\n"));
+
+    let mut bytes = Vec::new();
+    mesh.encode_ply(&mut bytes, Surface::Elements).unwrap();
+    let ss = String::from_utf8(bytes).unwrap();
+    assert!(ss.starts_with("ply\nformat ascii 1.0\n"));
+    assert!(ss.contains("element face 3\nproperty list uchar int vertex_indices\nproperty int physical\nend_header\n"));
+    assert!(ss.ends_with("3 0 1 2 1\n3 0 2 3 1\n4 0 1 2 3 3\n"));
+
+    let mut bytes = Vec::new();
+    mesh.encode_ply_binary(&mut bytes, Surface::Elements)
+        .unwrap();
+    let end = bytes
+        .windows(11)
+        .position(|w| w == b"end_header\n")
+        .unwrap()
+        + 11;
+    assert!(bytes.starts_with(b"ply\nformat binary_little_endian 1.0\n"));
+    assert_eq!(bytes.len(), end + 4 * 24 + 2 * 17 + 21);
+    assert_eq!(&bytes[bytes.len() - 4..], &3i32.to_le_bytes());
+}
+
+#[test]
+fn xdmf() {
+    let directory = std::env::temp_dir().join(format!("rutile-xdmf-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("square.xdmf");
+    let later = Data::new("temperature", 1).with_time(1, 0.5);
+    square()
+        .encode_xdmf(&path, &[temperature(), later], &[])
+        .unwrap();
+
+    let xml = std::fs::read_to_string(&path).unwrap();
+    assert!(xml.contains("CollectionType=\"Temporal\""));
+    assert_eq!(
+        xml.matches("<Grid Name=\"mesh\" GridType=\"Uniform\">")
+            .count(),
+        2
+    );
+    assert!(xml.contains("<Time Value=\"0.5\"/>"));
+    assert!(xml.contains("<Topology TopologyType=\"Mixed\" NumberOfElements=\"3\">\n<DataItem Format=\"Binary\" NumberType=\"Int\" Precision=\"8\" Endian=\"Little\" Dimensions=\"12\">square_topology.bin</DataItem>"));
+    assert!(xml.contains("Dimensions=\"4 3\">square_geometry.bin</DataItem>"));
+    assert!(xml.contains("<Attribute Name=\"temperature\" AttributeType=\"Scalar\" Center=\"Node\">\n<DataItem Format=\"Binary\" NumberType=\"Float\" Precision=\"8\" Endian=\"Little\" Dimensions=\"4\">square_nodes_1.bin</DataItem>"));
+
+    let read = |name: &str| std::fs::read(directory.join(name)).unwrap();
+    let topology: Vec<i64> = read("square_topology.bin")
+        .chunks(8)
+        .map(|b| i64::from_le_bytes(b.try_into().unwrap()))
+        .collect();
+    assert_eq!(topology, [4, 0, 1, 2, 4, 0, 2, 3, 2, 2, 0, 1]);
+    let temperature: Vec<f64> = read("square_nodes_0.bin")
+        .chunks(8)
+        .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+        .collect();
+    assert_eq!(temperature[..3], [1.0, 2.0, 3.0]);
+    assert!(temperature[3].is_nan());
+    assert_eq!(read("square_geometry.bin").len(), 4 * 3 * 8);
+
+    // A single family keeps its own topology type, without time.
+    let mut mesh = square();
+    mesh.elements.remove(&7);
+    mesh.encode_xdmf(&path, &[], &[]).unwrap();
+    let xml = std::fs::read_to_string(&path).unwrap();
+    assert!(!xml.contains("Temporal") && !xml.contains("<Time"));
+    assert!(xml.contains(
+        "<Topology TopologyType=\"Triangle\" NumberOfElements=\"2\" NodesPerElement=\"3\">"
+    ));
+    assert!(xml.contains("Dimensions=\"2 3\">square_topology.bin</DataItem>"));
+
+    let empty = Mesh::new(None, mesh::Nodes::new(), mesh::Elements::new());
+    assert!(empty
+        .encode_xdmf(directory.join("empty.xdmf"), &[], &[])
+        .is_err());
+    assert!(!directory.join("empty_geometry.bin").exists());
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn missing() {
+    let mut mesh = square();
+    mesh.elements
+        .insert(8, (1, 10, Topology::Triangle3(1, 2, 4)));
+    mesh.elements
+        .insert(9, (1, 10, Topology::Tetrahedron4(1, 2, 3, 4)));
+    let mut bytes = Vec::new();
+    assert!(mesh.encode_vtk(&mut bytes, &[], &[]).is_err());
+    assert!(mesh
+        .encode_vtu(&mut bytes, &[], &[], Encoding::Ascii)
+        .is_err());
+    assert!(mesh
+        .encode_stl(&mut bytes, Surface::Elements, false)
+        .is_err());
+    assert!(mesh.encode_medit(&mut bytes).is_err());
+    assert!(mesh.encode_nastran(&mut bytes, Field::Small).is_err());
+    assert!(mesh.poly().is_err());
+    assert!(mesh.encode_su2(&mut bytes).is_err());
+    assert!(mesh.encode_poly(&mut bytes).is_err());
+    assert!(mesh.encode_obj(&mut bytes, Surface::Elements).is_err());
+}
@@ -0,0 +1,181 @@
+//! Triangle and TetGen files: `.node`, `.ele`, `.face`, `.edge` and `.poly`.
+
+use {
+    crate::{
+        element::{Family, Physical, Topology},
+        encode,
+        mesh::Mesh,
+        node,
+    },
+    std::{
+        collections::{BTreeMap, HashMap},
+        fs::File,
+        io::{BufWriter, Write},
+        path::Path,
+    },
+};
+
+/// Dimension of the files written for `mesh`: 2 for Triangle, 3 for TetGen.
+fn dimension(mesh: &Mesh) -> i32 {
+    let dimension = mesh.elements.values().map(|(_, _, t)| t.dimension()).max();
+    dimension.unwrap_or(3).clamp(2, 3)
+}
+
+/// Elements of `family` with their physical tags, by element id.
+fn elements(mesh: &Mesh, family: Family) -> Vec<(Physical, Topology)> {
+    encode::elements(mesh)
+        .into_iter()
+        .filter(|(.., t)| t.family() == family)
+        .map(|(_, physical, _, topology)| (physical, topology))
+        .collect()
+}
+
+/// One-based indices of the nodes of `topology`.
+fn row(topology: &Topology, indices: &HashMap<node::Id, usize>) -> Result<String, std::io::Error> {
+    let nodes: Vec<String> = encode::positions(topology, indices)?
+        .iter()
+        .map(|position| (position + 1).to_string())
+        .collect();
+    Ok(nodes.join(" "))
+}
+
+/// Writes the nodes, numbered from 1, without attributes or markers.
+fn nodes<W: Write>(
+    mesh: &Mesh,
+    writer: &mut W,
+    dimension: i32,
+) -> Result<HashMap<node::Id, usize>, std::io::Error> {
+    let nodes = encode::nodes(mesh);
+    writeln!(writer, "{} {} 0 0", nodes.len(), dimension)?;
+    for (index, (_, node)) in nodes.iter().enumerate() {
+        let coordinates = &node.coordinates()[..dimension as usize];
+        let coordinates: Vec<String> = coordinates.iter().map(f64::to_string).collect();
+        writeln!(writer, "{} {}", index + 1, coordinates.join(" "))?;
+    }
+    Ok(encode::indices(&nodes))
+}
+
+/// Writes `elements` with their physical tags as boundary markers.
+fn markers<W: Write>(
+    writer: &mut W,
+    elements: &[(Physical, Topology)],
+    indices: &HashMap<node::Id, usize>,
+) -> Result<(), std::io::Error> {
+    writeln!(writer, "{} 1", elements.len())?;
+    for (index, (physical, topology)) in elements.iter().enumerate() {
+        writeln!(
+            writer,
+            "{} {} {}",
+            index + 1,
+            row(topology, indices)?,
+            physical
+        )?;
+    }
+    Ok(())
+}
+
+impl Mesh {
+    /// Writes the mesh as `<base>.node` and `<base>.ele`, with triangles for
+    /// Triangle if the mesh is planar and tetrahedra for TetGen otherwise,
+    /// and `<base>.face` and `<base>.edge` for the boundary triangles and
+    /// lines, if any.
+    ///
+    /// Nodes are numbered from 1 in id order; physical tags become the
+    /// element attribute and the boundary markers. Other elements are skipped.
+    pub fn encode_tetgen<P: AsRef<Path>>(&self, base: P) -> Result<(), std::io::Error> {
+        let base = base.as_ref();
+        let open =
+            |extension: &str| File::create(base.with_extension(extension)).map(BufWriter::new);
+        let dimension = dimension(self);
+
+        let mut writer = open("node")?;
+        let indices = nodes(self, &mut writer, dimension)?;
+        writer.flush()?;
+
+        let (family, corners) = match dimension {
+            2 => (Family::Triangle, 3),
+            _ => (Family::Tetrahedron, 4),
+        };
+        let mut writer = open("ele")?;
+        let cells = elements(self, family);
+        writeln!(writer, "{} {} 1", cells.len(), corners)?;
+        for (index, (physical, topology)) in cells.iter().enumerate() {
+            writeln!(
+                writer,
+                "{} {} {}",
+                index + 1,
+                row(topology, &indices)?,
+                physical
+            )?;
+        }
+        writer.flush()?;
+
+        if dimension == 3 {
+            let faces = elements(self, Family::Triangle);
+            if !faces.is_empty() {
+                let mut writer = open("face")?;
+                markers(&mut writer, &faces, &indices)?;
+                writer.flush()?;
+            }
+        }
+        let edges = elements(self, Family::Line);
+        if !edges.is_empty() {
+            let mut writer = open("edge")?;
+            markers(&mut writer, &edges, &indices)?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the boundary of the mesh as a `.poly` file: segments from the
+    /// lines of planar meshes for Triangle, facets from the triangles and
+    /// quadrangles of volume meshes for TetGen.
+    ///
+    /// Physical tags become boundary markers, and each physical tag of the
+    /// highest dimensional elements a region attribute, seeded at the
+    /// centroid of its first element.
+    pub fn encode_poly<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        let dimension = dimension(self);
+        let indices = nodes(self, writer, dimension)?;
+
+        if dimension == 2 {
+            markers(writer, &elements(self, Family::Line), &indices)?;
+        } else {
+            let mut facets = elements(self, Family::Triangle);
+            facets.extend(elements(self, Family::Quadrangle));
+            writeln!(writer, "{} 1", facets.len())?;
+            for (physical, topology) in &facets {
+                writeln!(writer, "1 0 {physical}")?;
+                writeln!(
+                    writer,
+                    "{} {}",
+                    topology.nodes().len(),
+                    row(topology, &indices)?
+                )?;
+            }
+        }
+        writeln!(writer, "0")?;
+
+        let mut regions: BTreeMap<Physical, [f64; 3]> = BTreeMap::new();
+        for (id, physical, _, topology) in encode::elements(self) {
+            if topology.dimension() == dimension && !regions.contains_key(&physical) {
+                if let Some(centroid) = self.centroid(id) {
+                    regions.insert(physical, centroid);
+                }
+            }
+        }
+        writeln!(writer, "{}", regions.len())?;
+        for (index, (physical, centroid)) in regions.iter().enumerate() {
+            let coordinates = &centroid[..dimension as usize];
+            let coordinates: Vec<String> = coordinates.iter().map(f64::to_string).collect();
+            writeln!(
+                writer,
+                "{} {} {} -1",
+                index + 1,
+                coordinates.join(" "),
+                physical
+            )?;
+        }
+        Ok(())
+    }
+}
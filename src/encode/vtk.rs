@@ -0,0 +1,109 @@
+//! Legacy VTK unstructured grids.
+
+use {
+    crate::{data::Data, element::Family, encode, mesh::Mesh},
+    std::io::Write,
+};
+
+/// VTK cell type of the elements of `family`; VTK orders the nodes of first
+/// order cells as gmsh does.
+pub(crate) const fn cell(family: Family) -> u8 {
+    match family {
+        Family::Point => 1,
+        Family::Line => 3,
+        Family::Triangle => 5,
+        Family::Quadrangle => 9,
+        Family::Tetrahedron => 10,
+        Family::Hexahedron => 12,
+        Family::Prism => 13,
+        Family::Pyramid => 14,
+    }
+}
+
+/// Name usable as a legacy VTK array name, which cannot contain whitespace.
+fn name(data: &Data) -> String {
+    data.name().replace(char::is_whitespace, "_")
+}
+
+impl Mesh {
+    /// Writes the mesh as an ASCII legacy VTK unstructured grid.
+    ///
+    /// Physical and elementary tags become the `gmsh:physical` and
+    /// `gmsh:geometrical` cell data, followed by the `elements` data; the
+    /// `nodes` data become point data. Missing values are written as NaN.
+    pub fn encode_vtk<W: Write>(
+        &self,
+        writer: &mut W,
+        nodes: &[Data],
+        elements: &[Data],
+    ) -> Result<(), std::io::Error> {
+        let ns = encode::nodes(self);
+        let indices = encode::indices(&ns);
+        let es = encode::elements(self);
+
+        writeln!(writer, "# vtk DataFile Version 2.0")?;
+        writeln!(writer, "rutile")?;
+        writeln!(writer, "ASCII")?;
+        writeln!(writer, "DATASET UNSTRUCTURED_GRID")?;
+
+        writeln!(writer, "POINTS {} double", ns.len())?;
+        for (_, node) in &ns {
+            writeln!(writer, "{} {} {}", node.x(), node.y(), node.z())?;
+        }
+
+        let size: usize = es.iter().map(|(.., t)| t.nodes().len() + 1).sum();
+        writeln!(writer, "CELLS {} {}", es.len(), size)?;
+        for (.., topology) in &es {
+            let positions = encode::positions(topology, &indices)?;
+            write!(writer, "{}", positions.len())?;
+            for position in positions {
+                write!(writer, " {position}")?;
+            }
+            writeln!(writer)?;
+        }
+        writeln!(writer, "CELL_TYPES {}", es.len())?;
+        for (.., topology) in &es {
+            writeln!(writer, "{}", cell(topology.family()))?;
+        }
+
+        writeln!(writer, "CELL_DATA {}", es.len())?;
+        writeln!(writer, "FIELD FieldData {}", 2 + elements.len())?;
+        writeln!(writer, "gmsh:physical 1 {} int", es.len())?;
+        for (_, physical, ..) in &es {
+            writeln!(writer, "{}", physical)?;
+        }
+        writeln!(writer, "gmsh:geometrical 1 {} int", es.len())?;
+        for (_, _, elementary, _) in &es {
+            writeln!(writer, "{}", elementary)?;
+        }
+        for data in elements {
+            let values = encode::values(data, es.iter().map(|&(id, ..)| id));
+            field(writer, data, es.len(), &values)?;
+        }
+
+        if !nodes.is_empty() {
+            writeln!(writer, "POINT_DATA {}", ns.len())?;
+            writeln!(writer, "FIELD FieldData {}", nodes.len())?;
+            for data in nodes {
+                let values = encode::values(data, ns.iter().map(|&(id, _)| id));
+                field(writer, data, ns.len(), &values)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn field<W: Write>(
+    writer: &mut W,
+    data: &Data,
+    tuples: usize,
+    values: &[f64],
+) -> Result<(), std::io::Error> {
+    let components = data.components();
+    writeln!(writer, "{} {} {} double", name(data), components, tuples)?;
+    for tuple in values.chunks(components.max(1)) {
+        let line: Vec<String> = tuple.iter().map(f64::to_string).collect();
+        writeln!(writer, "{}", line.join(" "))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,217 @@
+//! VTK XML unstructured grids.
+
+use {
+    crate::{base64, data::Data, encode, mesh::Mesh},
+    std::io::Write,
+};
+
+/// How the data arrays of XML VTK files are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Ascii,
+    /// Base64 inside each data array.
+    Base64,
+    /// Raw bytes in an appended data section at the end of the file.
+    Raw,
+}
+
+/// Values of a data array, with the VTK type of their elements.
+#[derive(Clone, Debug, PartialEq)]
+enum Values {
+    Float64(Vec<f64>),
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    UInt8(Vec<u8>),
+}
+
+impl Values {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Float64(_) => "Float64",
+            Self::Int32(_) => "Int32",
+            Self::Int64(_) => "Int64",
+            Self::UInt8(_) => "UInt8",
+        }
+    }
+
+    fn ascii(&self) -> String {
+        fn join<T: ToString>(values: &[T]) -> String {
+            let values: Vec<String> = values.iter().map(T::to_string).collect();
+            values.join(" ")
+        }
+        match self {
+            Self::Float64(values) => join(values),
+            Self::Int32(values) => join(values),
+            Self::Int64(values) => join(values),
+            Self::UInt8(values) => join(values),
+        }
+    }
+
+    /// Little endian bytes, preceded by their count as the `UInt64` header.
+    fn bytes(&self) -> Vec<u8> {
+        let data: Vec<u8> = match self {
+            Self::Float64(values) => values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+            Self::Int32(values) => values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+            Self::Int64(values) => values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+            Self::UInt8(values) => values.clone(),
+        };
+        let mut bytes = (data.len() as u64).to_le_bytes().to_vec();
+        bytes.extend(data);
+        bytes
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Array {
+    name: String,
+    components: usize,
+    values: Values,
+}
+
+impl Array {
+    fn new<S: Into<String>>(name: S, components: usize, values: Values) -> Self {
+        Self {
+            name: name.into(),
+            components,
+            values,
+        }
+    }
+
+    fn data(data: &Data, values: Vec<f64>) -> Self {
+        Self::new(data.name(), data.components(), Values::Float64(values))
+    }
+}
+
+/// Writer of data arrays, collecting the appended data of raw files.
+struct Arrays {
+    encoding: Encoding,
+    appended: Vec<u8>,
+}
+
+impl Arrays {
+    fn write<W: Write>(&mut self, writer: &mut W, array: &Array) -> Result<(), std::io::Error> {
+        write!(
+            writer,
+            "<DataArray type=\"{}\" Name=\"{}\" NumberOfComponents=\"{}\"",
+            array.values.kind(),
+            escape(&array.name),
+            array.components
+        )?;
+        match self.encoding {
+            Encoding::Ascii => {
+                writeln!(writer, " format=\"ascii\">")?;
+                writeln!(writer, "{}", array.values.ascii())?;
+            }
+            Encoding::Base64 => {
+                writeln!(writer, " format=\"binary\">")?;
+                writeln!(writer, "{}", base64::encode(&array.values.bytes()))?;
+            }
+            Encoding::Raw => {
+                writeln!(
+                    writer,
+                    " format=\"appended\" offset=\"{}\">",
+                    self.appended.len()
+                )?;
+                self.appended.extend(array.values.bytes());
+            }
+        }
+        writeln!(writer, "</DataArray>")
+    }
+}
+
+/// `name` with the characters reserved in XML attributes escaped.
+pub(crate) fn escape(name: &str) -> String {
+    name.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Mesh {
+    /// Writes the mesh as a VTK XML unstructured grid, `.vtu`, with its data
+    /// arrays stored following `encoding`.
+    ///
+    /// Physical and elementary tags become the `gmsh:physical` and
+    /// `gmsh:geometrical` cell data, followed by the `elements` data; the
+    /// `nodes` data become point data. Missing values are written as NaN.
+    pub fn encode_vtu<W: Write>(
+        &self,
+        writer: &mut W,
+        nodes: &[Data],
+        elements: &[Data],
+        encoding: Encoding,
+    ) -> Result<(), std::io::Error> {
+        let ns = encode::nodes(self);
+        let indices = encode::indices(&ns);
+        let es = encode::elements(self);
+        let mut arrays = Arrays {
+            encoding,
+            appended: Vec::new(),
+        };
+
+        writeln!(writer, "<?xml version=\"1.0\"?>")?;
+        writeln!(
+            writer,
+            "<VTKFile type=\"UnstructuredGrid\" version=\"1.0\" byte_order=\"LittleEndian\" header_type=\"UInt64\">"
+        )?;
+        writeln!(writer, "<UnstructuredGrid>")?;
+        writeln!(
+            writer,
+            "<Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">",
+            ns.len(),
+            es.len()
+        )?;
+
+        writeln!(writer, "<PointData>")?;
+        for data in nodes {
+            let values = encode::values(data, ns.iter().map(|&(id, _)| id));
+            arrays.write(writer, &Array::data(data, values))?;
+        }
+        writeln!(writer, "</PointData>")?;
+
+        writeln!(writer, "<CellData>")?;
+        let physical = es.iter().map(|&(_, physical, ..)| physical).collect();
+        arrays.write(
+            writer,
+            &Array::new("gmsh:physical", 1, Values::Int32(physical)),
+        )?;
+        let elementary = es.iter().map(|&(_, _, elementary, _)| elementary).collect();
+        let elementary = Array::new("gmsh:geometrical", 1, Values::Int32(elementary));
+        arrays.write(writer, &elementary)?;
+        for data in elements {
+            let values = encode::values(data, es.iter().map(|&(id, ..)| id));
+            arrays.write(writer, &Array::data(data, values))?;
+        }
+        writeln!(writer, "</CellData>")?;
+
+        writeln!(writer, "<Points>")?;
+        let points = ns.iter().flat_map(|(_, n)| n.coordinates()).collect();
+        arrays.write(writer, &Array::new("Points", 3, Values::Float64(points)))?;
+        writeln!(writer, "</Points>")?;
+
+        writeln!(writer, "<Cells>")?;
+        let mut connectivity = Vec::new();
+        let mut offsets = Vec::new();
+        let mut types = Vec::new();
+        for (.., topology) in &es {
+            let positions = encode::positions(topology, &indices)?;
+            connectivity.extend(positions.into_iter().map(|p| p as i64));
+            offsets.push(connectivity.len() as i64);
+            types.push(encode::vtk::cell(topology.family()));
+        }
+        let connectivity = Array::new("connectivity", 1, Values::Int64(connectivity));
+        arrays.write(writer, &connectivity)?;
+        arrays.write(writer, &Array::new("offsets", 1, Values::Int64(offsets)))?;
+        arrays.write(writer, &Array::new("types", 1, Values::UInt8(types)))?;
+        writeln!(writer, "</Cells>")?;
+
+        writeln!(writer, "</Piece>")?;
+        writeln!(writer, "</UnstructuredGrid>")?;
+        if encoding == Encoding::Raw {
+            write!(writer, "<AppendedData encoding=\"raw\">\n_")?;
+            writer.write_all(&arrays.appended)?;
+            writeln!(writer, "\n</AppendedData>")?;
+        }
+        writeln!(writer, "</VTKFile>")
+    }
+}
@@ -0,0 +1,275 @@
+//! XDMF light data in XML, with the heavy data in raw binary files.
+
+use {
+    crate::{
+        data::Data,
+        element::Family,
+        encode::{self, vtu::escape},
+        mesh::Mesh,
+    },
+    std::{
+        fs::File,
+        io::{BufWriter, Write},
+        path::Path,
+    },
+};
+
+/// XDMF topology type and mixed topology code of `family`.
+const fn kind(family: Family) -> (&'static str, i64) {
+    match family {
+        Family::Point => ("Polyvertex", 1),
+        Family::Line => ("Polyline", 2),
+        Family::Triangle => ("Triangle", 4),
+        Family::Quadrangle => ("Quadrilateral", 5),
+        Family::Tetrahedron => ("Tetrahedron", 6),
+        Family::Pyramid => ("Pyramid", 7),
+        Family::Prism => ("Wedge", 8),
+        Family::Hexahedron => ("Hexahedron", 9),
+    }
+}
+
+/// XDMF attribute type of data with `components` values.
+const fn attribute(components: usize) -> &'static str {
+    match components {
+        1 => "Scalar",
+        3 => "Vector",
+        6 => "Tensor6",
+        9 => "Tensor",
+        _ => "Matrix",
+    }
+}
+
+/// Heavy data file, with the type of its little endian values.
+struct Heavy {
+    file: String,
+    number: &'static str,
+    dimensions: String,
+}
+
+impl Heavy {
+    fn write<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        writeln!(
+            writer,
+            "<DataItem Format=\"Binary\" NumberType=\"{}\" Precision=\"8\" Endian=\"Little\" Dimensions=\"{}\">{}</DataItem>",
+            self.number,
+            self.dimensions,
+            escape(&self.file)
+        )
+    }
+}
+
+/// Writer of the heavy data files next to the `.xdmf` file.
+struct Files<'a> {
+    directory: &'a Path,
+    stem: String,
+}
+
+impl Files<'_> {
+    fn create(&self, name: &str, bytes: &[u8]) -> Result<String, std::io::Error> {
+        let file = format!("{}_{name}.bin", self.stem);
+        let mut writer = BufWriter::new(File::create(self.directory.join(&file))?);
+        writer.write_all(bytes)?;
+        writer.flush()?;
+        Ok(file)
+    }
+
+    fn reals(
+        &self,
+        name: &str,
+        values: &[f64],
+        dimensions: String,
+    ) -> Result<Heavy, std::io::Error> {
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        Ok(Heavy {
+            file: self.create(name, &bytes)?,
+            number: "Float",
+            dimensions,
+        })
+    }
+
+    fn integers(
+        &self,
+        name: &str,
+        values: &[i64],
+        dimensions: String,
+    ) -> Result<Heavy, std::io::Error> {
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        Ok(Heavy {
+            file: self.create(name, &bytes)?,
+            number: "Int",
+            dimensions,
+        })
+    }
+}
+
+impl Mesh {
+    /// Writes the mesh as the XDMF file `path`, with coordinates, connectivity
+    /// and data stored in raw little endian binary files next to it, named
+    /// after its stem.
+    ///
+    /// Meshes of several element families get a mixed topology. Physical and
+    /// elementary tags become the `gmsh:physical` and `gmsh:geometrical`
+    /// cell attributes, the `nodes` and `elements` data node and cell
+    /// attributes. Data sharing a step go to the same grid of a temporal
+    /// collection, at their time; missing values are written as NaN.
+    ///
+    /// Fails on a mesh without elements, which XDMF cannot describe.
+    pub fn encode_xdmf<P: AsRef<Path>>(
+        &self,
+        path: P,
+        nodes: &[Data],
+        elements: &[Data],
+    ) -> Result<(), std::io::Error> {
+        if self.elements.is_empty() {
+            return Err(std::io::Error::other(
+                "failed to encode mesh: XDMF grids need elements",
+            ));
+        }
+        let path = path.as_ref();
+        let files = Files {
+            directory: path.parent().unwrap_or(Path::new("")),
+            stem: path
+                .file_stem()
+                .map_or_else(String::new, |s| s.to_string_lossy().into_owned()),
+        };
+        let ns = encode::nodes(self);
+        let indices = encode::indices(&ns);
+        let es = encode::elements(self);
+
+        let points: Vec<f64> = ns.iter().flat_map(|(_, n)| n.coordinates()).collect();
+        let geometry = files.reals("geometry", &points, format!("{} 3", ns.len()))?;
+
+        let mut families: Vec<Family> = es.iter().map(|(.., t)| t.family()).collect();
+        families.sort_unstable_by_key(|&family| kind(family).1);
+        families.dedup();
+        let mixed = families.len() > 1;
+        let mut connectivity = Vec::new();
+        for (.., topology) in &es {
+            let nodes = topology.nodes();
+            if mixed {
+                let code = kind(topology.family()).1;
+                connectivity.push(code);
+                if matches!(topology.family(), Family::Point | Family::Line) {
+                    connectivity.push(nodes.len() as i64);
+                }
+            }
+            let positions = encode::positions(topology, &indices)?;
+            connectivity.extend(positions.into_iter().map(|p| p as i64));
+        }
+        let (name, per) = match families.first() {
+            Some(&family) if !mixed => (kind(family).0, family.vertices()),
+            _ => ("Mixed", 0),
+        };
+        let dimensions = match mixed {
+            true => connectivity.len().to_string(),
+            false => format!("{} {}", es.len(), per),
+        };
+        let topology = files.integers("topology", &connectivity, dimensions)?;
+
+        let physical: Vec<i64> = es.iter().map(|&(_, p, ..)| p as i64).collect();
+        let physical = files.integers("physical", &physical, es.len().to_string())?;
+        let elementary: Vec<i64> = es.iter().map(|&(_, _, e, _)| e as i64).collect();
+        let elementary = files.integers("elementary", &elementary, es.len().to_string())?;
+
+        let mut attributes = Vec::new();
+        for (center, kind, data, ids) in [
+            (
+                "Node",
+                "nodes",
+                nodes,
+                ns.iter().map(|&(id, _)| id).collect::<Vec<_>>(),
+            ),
+            (
+                "Cell",
+                "elements",
+                elements,
+                es.iter().map(|&(id, ..)| id).collect(),
+            ),
+        ] {
+            for (index, data) in data.iter().enumerate() {
+                let values = encode::values(data, ids.iter().copied());
+                let dimensions = match data.components() {
+                    1 => ids.len().to_string(),
+                    c => format!("{} {c}", ids.len()),
+                };
+                let heavy = files.reals(&format!("{kind}_{index}"), &values, dimensions)?;
+                attributes.push((center, data, heavy));
+            }
+        }
+        let mut steps: Vec<(i32, f64)> = attributes
+            .iter()
+            .map(|(_, data, _)| (data.step(), data.time()))
+            .collect();
+        steps.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        steps.dedup_by_key(|&mut (step, _)| step);
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "<?xml version=\"1.0\"?>")?;
+        writeln!(writer, "<Xdmf Version=\"3.0\">")?;
+        writeln!(writer, "<Domain>")?;
+        let grids: Vec<Option<(i32, f64)>> = match steps.is_empty() {
+            true => vec![None],
+            false => {
+                writeln!(
+                    writer,
+                    "<Grid Name=\"mesh\" GridType=\"Collection\" CollectionType=\"Temporal\">"
+                )?;
+                steps.iter().copied().map(Some).collect()
+            }
+        };
+        for grid in &grids {
+            writeln!(writer, "<Grid Name=\"mesh\" GridType=\"Uniform\">")?;
+            if let Some((_, time)) = grid {
+                writeln!(writer, "<Time Value=\"{time}\"/>")?;
+            }
+            match mixed {
+                true => writeln!(
+                    writer,
+                    "<Topology TopologyType=\"Mixed\" NumberOfElements=\"{}\">",
+                    es.len()
+                )?,
+                false => writeln!(
+                    writer,
+                    "<Topology TopologyType=\"{name}\" NumberOfElements=\"{}\" NodesPerElement=\"{per}\">",
+                    es.len()
+                )?,
+            }
+            topology.write(&mut writer)?;
+            writeln!(writer, "</Topology>")?;
+            writeln!(writer, "<Geometry GeometryType=\"XYZ\">")?;
+            geometry.write(&mut writer)?;
+            writeln!(writer, "</Geometry>")?;
+            for (name, heavy) in [
+                ("gmsh:physical", &physical),
+                ("gmsh:geometrical", &elementary),
+            ] {
+                writeln!(
+                    writer,
+                    "<Attribute Name=\"{name}\" AttributeType=\"Scalar\" Center=\"Cell\">"
+                )?;
+                heavy.write(&mut writer)?;
+                writeln!(writer, "</Attribute>")?;
+            }
+            let members = attributes
+                .iter()
+                .filter(|(_, data, _)| grid.is_none_or(|(step, _)| data.step() == step));
+            for (center, data, heavy) in members {
+                writeln!(
+                    writer,
+                    "<Attribute Name=\"{}\" AttributeType=\"{}\" Center=\"{center}\">",
+                    escape(data.name()),
+                    attribute(data.components())
+                )?;
+                heavy.write(&mut writer)?;
+                writeln!(writer, "</Attribute>")?;
+            }
+            writeln!(writer, "</Grid>")?;
+        }
+        if !steps.is_empty() {
+            writeln!(writer, "</Grid>")?;
+        }
+        writeln!(writer, "</Domain>")?;
+        writeln!(writer, "</Xdmf>")?;
+        writer.flush()
+    }
+}
@@ -1,4 +1,6 @@
+/// Version, file type and data size of the gmsh file a mesh was read from.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Format {
     version: f64,
     file: i32,
@@ -0,0 +1,65 @@
+//! Small vector helpers shared by the geometric algorithms.
+
+pub(crate) type Point = [f64; 3];
+
+pub(crate) fn add(a: Point, b: Point) -> Point {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+pub(crate) fn sub(a: Point, b: Point) -> Point {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub(crate) fn scale(a: Point, s: f64) -> Point {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+pub(crate) fn dot(a: Point, b: Point) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub(crate) fn cross(a: Point, b: Point) -> Point {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+pub(crate) fn norm(a: Point) -> f64 {
+    dot(a, a).sqrt()
+}
+
+pub(crate) fn distance(a: Point, b: Point) -> f64 {
+    norm(sub(a, b))
+}
+
+pub(crate) fn det(a: Point, b: Point, c: Point) -> f64 {
+    dot(a, cross(b, c))
+}
+
+pub(crate) fn centroid(points: &[Point]) -> Point {
+    let sum = points.iter().fold([0.0; 3], |acc, &p| add(acc, p));
+    scale(sum, 1.0 / points.len() as f64)
+}
+
+/// Area-weighted normal of a (possibly non-planar) polygon, Newell's method.
+pub(crate) fn newell(points: &[Point]) -> Point {
+    let mut n = [0.0; 3];
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        n[0] += (a[1] - b[1]) * (a[2] + b[2]);
+        n[1] += (a[2] - b[2]) * (a[0] + b[0]);
+        n[2] += (a[0] - b[0]) * (a[1] + b[1]);
+    }
+    scale(n, 0.5)
+}
+
+/// Angle between two vectors, in radians.
+pub(crate) fn angle(a: Point, b: Point) -> f64 {
+    let lengths = norm(a) * norm(b);
+    if lengths == 0.0 {
+        return 0.0;
+    }
+    (dot(a, b) / lengths).clamp(-1.0, 1.0).acos()
+}
@@ -1,8 +1,55 @@
+mod base64;
 mod decode;
+mod encode;
+pub use encode::{nastran::Field, vtu::Encoding, Surface};
 
 pub(crate) mod mesh;
 pub use mesh::Mesh;
 
+pub(crate) mod builder;
+pub use builder::{Invalid, MeshBuilder};
+
+#[cfg(any(feature = "nalgebra", feature = "ndarray"))]
+pub(crate) mod array;
+
+pub(crate) mod adjacency;
+pub use adjacency::{Adjacency, Connection};
+
+pub(crate) mod boundary;
+pub use boundary::Facet;
+
+pub(crate) mod data;
+pub use data::Data;
+
+pub(crate) mod locate;
+pub use locate::{Location, Locator};
+
+pub(crate) mod merge;
+pub use merge::Merged;
+
+pub(crate) mod numbering;
+pub use numbering::{Edges, Faces, Incidence, Numbering};
+
+pub(crate) mod quadrature;
+pub use quadrature::Quadrature;
+
+pub(crate) mod quality;
+pub use quality::{Histogram, Metric, Quality, Statistics};
+
+pub(crate) mod renumber;
+pub use renumber::{Permutation, Renumbered, Reordering};
+
+pub(crate) mod submesh;
+pub use submesh::{Filter, Submesh};
+
 pub(crate) mod element;
+pub use element::{Family, Topology};
+
 pub(crate) mod format;
+pub use format::Format;
+pub(crate) mod geometry;
 pub(crate) mod node;
+pub use node::Node;
+
+pub(crate) mod shape;
+pub use shape::Lagrange;
@@ -0,0 +1,412 @@
+//! Point location through a bounding volume hierarchy over the elements.
+
+use {
+    crate::{
+        element::{self, Family},
+        geometry::{self, Point},
+        mesh::Mesh,
+        shape,
+    },
+    std::{cmp::Reverse, collections::BinaryHeap, ops::Range},
+};
+
+#[cfg(test)]
+mod test;
+
+/// Relative tolerance on reference coordinates and distances.
+const TOLERANCE: f64 = 1e-9;
+const LEAF: usize = 4;
+
+/// Element containing a point, and where in it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Location {
+    element: element::Id,
+    coordinates: Point,
+    weights: Vec<f64>,
+}
+
+impl Location {
+    pub fn element(&self) -> element::Id {
+        self.element
+    }
+
+    /// Coordinates in the gmsh reference element.
+    pub fn coordinates(&self) -> [f64; 3] {
+        self.coordinates
+    }
+
+    /// Shape function values at the point, one per element node; these are
+    /// the barycentric coordinates of simplices.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Bounds {
+    lower: Point,
+    upper: Point,
+}
+
+impl Bounds {
+    fn new(points: &[Point]) -> Self {
+        points.iter().fold(
+            Self {
+                lower: [f64::INFINITY; 3],
+                upper: [f64::NEG_INFINITY; 3],
+            },
+            |b, p| Self {
+                lower: [0, 1, 2].map(|a| b.lower[a].min(p[a])),
+                upper: [0, 1, 2].map(|a| b.upper[a].max(p[a])),
+            },
+        )
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        Self {
+            lower: [0, 1, 2].map(|a| self.lower[a].min(other.lower[a])),
+            upper: [0, 1, 2].map(|a| self.upper[a].max(other.upper[a])),
+        }
+    }
+
+    fn diagonal(&self) -> f64 {
+        geometry::distance(self.lower, self.upper)
+    }
+
+    /// Distance from `p` to the box, zero inside.
+    fn distance(&self, p: Point) -> f64 {
+        (0..3)
+            .map(|a| (self.lower[a] - p[a]).max(p[a] - self.upper[a]).max(0.0))
+            .map(|d| d * d)
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    fn contains(&self, p: Point, margin: f64) -> bool {
+        (0..3).all(|a| p[a] >= self.lower[a] - margin && p[a] <= self.upper[a] + margin)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Item {
+    id: element::Id,
+    family: Family,
+    points: Vec<Point>,
+    bounds: Bounds,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Node {
+    bounds: Bounds,
+    items: Range<usize>,
+    children: Option<(usize, usize)>,
+}
+
+/// Spatial index over the highest dimensional elements of a mesh.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Locator {
+    items: Vec<Item>,
+    nodes: Vec<Node>,
+    margin: f64,
+}
+
+impl Locator {
+    pub fn new(mesh: &Mesh) -> Self {
+        let dimension = mesh
+            .elements
+            .values()
+            .map(|(_, _, t)| t.dimension())
+            .max()
+            .unwrap_or(0);
+
+        let mut items: Vec<Item> = mesh
+            .elements
+            .iter()
+            .filter(|(_, (_, _, t))| t.dimension() == dimension)
+            .filter_map(|(id, (_, _, topology))| {
+                let points = mesh.points(topology)?;
+                Some(Item {
+                    id: *id,
+                    family: topology.family(),
+                    bounds: Bounds::new(&points),
+                    points,
+                })
+            })
+            .collect();
+        items.sort_by_key(|item| item.id);
+
+        let mut nodes = Vec::new();
+        if !items.is_empty() {
+            let length = items.len();
+            build(&mut items, 0..length, &mut nodes);
+        }
+        let margin = nodes.first().map_or(0.0, |n: &Node| n.bounds.diagonal()) * TOLERANCE;
+
+        Self {
+            items,
+            nodes,
+            margin,
+        }
+    }
+
+    /// Element containing `p`, the one with the lowest id if several do.
+    pub fn locate(&self, p: [f64; 3]) -> Option<Location> {
+        let mut candidates = self.candidates(p);
+        candidates.sort_by_key(|&i| self.items[i].id);
+        candidates.into_iter().find_map(|i| {
+            let item = &self.items[i];
+            let coordinates = invert(item.family, &item.points, p)?;
+            if !shape::contains(item.family, coordinates, TOLERANCE) {
+                return None;
+            }
+            Some(Location {
+                element: item.id,
+                coordinates,
+                weights: shape::linear(item.family).values(coordinates),
+            })
+        })
+    }
+
+    /// Element nearest to `p`, at its point closest to `p`; the element
+    /// containing `p` if there is one.
+    ///
+    /// The closest point is exact for elements with an affine mapping and
+    /// approximate for distorted ones.
+    pub fn nearest(&self, p: [f64; 3]) -> Option<Location> {
+        if let Some(location) = self.locate(p) {
+            return Some(location);
+        }
+
+        // Distances are never negative, so their bits sort like their values.
+        let key = |d: f64| d.to_bits();
+        let mut best: Option<(f64, usize, Point)> = None;
+        let mut heap = BinaryHeap::new();
+        if let Some(root) = self.nodes.first() {
+            heap.push(Reverse((key(root.bounds.distance(p)), 0)));
+        }
+        while let Some(Reverse((distance, n))) = heap.pop() {
+            if best.is_some_and(|(d, _, _)| f64::from_bits(distance) > d) {
+                break;
+            }
+            let node = &self.nodes[n];
+            if let Some((left, right)) = node.children {
+                for child in [left, right] {
+                    heap.push(Reverse((key(self.nodes[child].bounds.distance(p)), child)));
+                }
+                continue;
+            }
+            for i in node.items.clone() {
+                let item = &self.items[i];
+                if best.is_some_and(|(d, _, _)| item.bounds.distance(p) > d) {
+                    continue;
+                }
+                let Some(x) = closest(item.family, &item.points, p) else {
+                    continue;
+                };
+                let Some(xi) = project(item.family, &item.points, x) else {
+                    continue;
+                };
+                let xi = shape::clamp(item.family, xi);
+                let d = geometry::distance(x, p);
+                // Equally near items may lie in different leaves; the lowest id wins.
+                let closer =
+                    best.is_none_or(|(b, j, _)| d < b || (d == b && item.id < self.items[j].id));
+                if closer {
+                    best = Some((d, i, xi));
+                }
+            }
+        }
+
+        best.map(|(_, i, coordinates)| Location {
+            element: self.items[i].id,
+            coordinates,
+            weights: shape::linear(self.items[i].family).values(coordinates),
+        })
+    }
+
+    /// Items whose bounds contain `p`.
+    fn candidates(&self, p: Point) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !node.bounds.contains(p, self.margin) {
+                continue;
+            }
+            match node.children {
+                Some((left, right)) => stack.extend([left, right]),
+                None => found.extend(
+                    node.items
+                        .clone()
+                        .filter(|&i| self.items[i].bounds.contains(p, self.margin)),
+                ),
+            }
+        }
+        found
+    }
+}
+
+impl Mesh {
+    pub fn locator(&self) -> Locator {
+        Locator::new(self)
+    }
+}
+
+/// Builds the subtree over `items[range]`, returning the index of its root.
+fn build(items: &mut [Item], range: Range<usize>, nodes: &mut Vec<Node>) -> usize {
+    let bounds = items[range.clone()]
+        .iter()
+        .map(|item| item.bounds)
+        .reduce(|a, b| a.union(&b))
+        .unwrap_or(Bounds::new(&[]));
+
+    let index = nodes.len();
+    nodes.push(Node {
+        bounds,
+        items: range.clone(),
+        children: None,
+    });
+    if range.len() <= LEAF {
+        return index;
+    }
+
+    let centre = |item: &Item| geometry::centroid(&[item.bounds.lower, item.bounds.upper]);
+    let extent = geometry::sub(bounds.upper, bounds.lower);
+    let axis = (0..3)
+        .max_by(|&a, &b| extent[a].total_cmp(&extent[b]))
+        .unwrap_or(0);
+    items[range.clone()].sort_by(|a, b| centre(a)[axis].total_cmp(&centre(b)[axis]));
+
+    let middle = range.start + range.len() / 2;
+    let left = build(items, range.start..middle, nodes);
+    let right = build(items, middle..range.end, nodes);
+    nodes[index].children = Some((left, right));
+    index
+}
+
+/// Reference coordinates of `p` in the element with vertices `points`,
+/// `None` if the mapping is singular or `p` lies off a lower dimensional
+/// element.
+pub(crate) fn invert(family: Family, points: &[Point], p: Point) -> Option<Point> {
+    let xi = project(family, points, p)?;
+    let size = Bounds::new(points).diagonal().max(f64::MIN_POSITIVE);
+    if geometry::distance(map(family, points, xi), p) > TOLERANCE * size {
+        return None;
+    }
+    Some(xi)
+}
+
+/// Reference coordinates minimising the distance between `p` and their
+/// image, possibly outside the reference element; `None` if the mapping is
+/// singular.
+fn project(family: Family, points: &[Point], p: Point) -> Option<Point> {
+    let dimension = family.dimension() as usize;
+    let mut xi = geometry::centroid(&shape::reference(family));
+
+    for _ in 0..50 {
+        let gradients = shape::linear(family).gradients(xi);
+        let residual = geometry::sub(map(family, points, xi), p);
+
+        // Columns of the Jacobian of the reference to physical mapping.
+        let columns: Vec<Point> = (0..dimension)
+            .map(|b| {
+                points
+                    .iter()
+                    .zip(gradients.iter())
+                    .fold([0.0; 3], |acc, (q, g)| {
+                        geometry::add(acc, geometry::scale(*q, g[b]))
+                    })
+            })
+            .collect();
+        let normal: Vec<Vec<f64>> = columns
+            .iter()
+            .map(|a| columns.iter().map(|b| geometry::dot(*a, *b)).collect())
+            .collect();
+        let rhs: Vec<f64> = columns
+            .iter()
+            .map(|a| geometry::dot(*a, residual))
+            .collect();
+        let step = solve(normal, rhs)?;
+
+        for (b, s) in step.iter().enumerate() {
+            xi[b] -= s;
+        }
+        if step.iter().map(|s| s * s).sum::<f64>().sqrt() < 1e-14 {
+            break;
+        }
+    }
+    Some(xi)
+}
+
+/// Point of the element with vertices `points` closest to `p`: the projection
+/// of `p` if it falls inside, otherwise the closest point of its facets.
+fn closest(family: Family, points: &[Point], p: Point) -> Option<Point> {
+    let xi = project(family, points, p);
+    if let Some(xi) = xi.filter(|&xi| shape::contains(family, xi, TOLERANCE)) {
+        return Some(map(family, points, xi));
+    }
+    let facets: Vec<Vec<usize>> = match family.dimension() {
+        3 => family.faces().iter().map(|f| f.to_vec()).collect(),
+        2 => family.edges().iter().map(|e| e.to_vec()).collect(),
+        1 => vec![vec![0], vec![1]],
+        _ => vec![],
+    };
+    facets
+        .into_iter()
+        .filter_map(|facet| {
+            let family = match facet.len() {
+                1 => Family::Point,
+                2 => Family::Line,
+                3 => Family::Triangle,
+                _ => Family::Quadrangle,
+            };
+            let points: Vec<Point> = facet.iter().map(|&v| points[v]).collect();
+            closest(family, &points, p)
+        })
+        .min_by(|a, b| geometry::distance(*a, p).total_cmp(&geometry::distance(*b, p)))
+}
+
+/// Image of the reference coordinates `xi` in the element with vertices
+/// `points`.
+fn map(family: Family, points: &[Point], xi: Point) -> Point {
+    points
+        .iter()
+        .zip(shape::linear(family).values(xi))
+        .fold([0.0; 3], |acc, (q, n)| {
+            geometry::add(acc, geometry::scale(*q, n))
+        })
+}
+
+/// Solution of the small dense system `a x = b`, by Gaussian elimination
+/// with partial pivoting.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for column in 0..n {
+        let pivot =
+            (column..n).max_by(|&i, &j| a[i][column].abs().total_cmp(&a[j][column].abs()))?;
+        if a[pivot][column].abs() < f64::MIN_POSITIVE {
+            return None;
+        }
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+        let (upper, lower) = a.split_at_mut(column + 1);
+        let pivot = &upper[column];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[column] / pivot[column];
+            for (x, p) in row[column..].iter_mut().zip(&pivot[column..]) {
+                *x -= factor * p;
+            }
+            b[column + 1 + offset] -= factor * b[column];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
@@ -0,0 +1,113 @@
+use crate::{
+    element::Topology,
+    mesh::{self, Mesh},
+    node::Node,
+};
+
+fn close(a: [f64; 3], b: [f64; 3]) -> bool {
+    (0..3).all(|i| (a[i] - b[i]).abs() < 1e-9)
+}
+
+// A row of unit cells along x: a hexahedron, a prism, a pyramid and a
+// tetrahedron.
+fn mesh() -> Mesh {
+    let mut ns = mesh::Nodes::new();
+    let mut id = 0;
+    for x in 0..4 {
+        for (y, z) in [(0, 0), (1, 0), (1, 1), (0, 1)] {
+            id += 1;
+            ns.insert(id, Node::new(x as f64, y as f64, z as f64));
+        }
+    }
+    ns.insert(17, Node::new(2.5, 0.5, 0.5));
+    ns.insert(18, Node::new(4.0, 0.0, 0.0));
+
+    let mut es = mesh::Elements::new();
+    // Hexahedron over 0 <= x <= 1, with gmsh ordering.
+    es.insert(1, (1, 1, Topology::Hexahedron8(1, 5, 6, 2, 4, 8, 7, 3)));
+    // Prism over 1 <= x <= 2, triangles (y, z) = (0, 0), (1, 0), (0, 1).
+    es.insert(2, (1, 1, Topology::Prism6(5, 6, 8, 9, 10, 12)));
+    // Pyramid over 2 <= x <= 3, base at x = 2 and apex at the centre.
+    es.insert(3, (1, 1, Topology::Pyramid5(9, 10, 11, 12, 17)));
+    es.insert(4, (1, 1, Topology::Tetrahedron4(13, 14, 16, 18)));
+    es.insert(5, (2, 2, Topology::Triangle3(1, 2, 3)));
+
+    Mesh::new(None, ns, es)
+}
+
+#[test]
+fn locate() {
+    let locator = mesh().locator();
+
+    let hexahedron = locator.locate([0.25, 0.5, 0.75]).unwrap();
+    assert_eq!(hexahedron.element(), 1);
+    assert!(close(hexahedron.coordinates(), [-0.5, 0.0, 0.5]));
+    assert!((hexahedron.weights().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+    let prism = locator.locate([1.5, 0.25, 0.5]).unwrap();
+    assert_eq!(prism.element(), 2);
+    assert!(close(prism.coordinates(), [0.25, 0.5, 0.0]));
+
+    let pyramid = locator.locate([2.25, 0.5, 0.5]).unwrap();
+    assert_eq!(pyramid.element(), 3);
+    assert!(close(pyramid.coordinates(), [0.0, 0.0, 0.5]));
+
+    let tetrahedron = locator.locate([3.1, 0.2, 0.1]).unwrap();
+    assert_eq!(tetrahedron.element(), 4);
+    assert!(close(tetrahedron.coordinates(), [0.2, 0.1, 0.1]));
+    assert!(close(
+        [
+            tetrahedron.weights()[0],
+            tetrahedron.weights()[1],
+            tetrahedron.weights()[2]
+        ],
+        [0.6, 0.2, 0.1]
+    ));
+
+    // Shared faces resolve to the lowest id.
+    assert_eq!(locator.locate([1.0, 0.2, 0.2]).unwrap().element(), 1);
+
+    assert!(locator.locate([1.5, 0.9, 0.9]).is_none());
+    assert!(locator.locate([-1.0, 0.0, 0.0]).is_none());
+}
+
+#[test]
+fn surface() {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 1.0));
+    ns.insert(2, Node::new(2.0, 0.0, 1.0));
+    ns.insert(3, Node::new(2.0, 2.0, 1.0));
+    ns.insert(4, Node::new(0.0, 2.0, 1.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Quadrangle4(1, 2, 3, 4)));
+
+    let locator = Mesh::new(None, ns, es).locator();
+    let location = locator.locate([1.5, 0.5, 1.0]).unwrap();
+    assert!(close(location.coordinates(), [0.5, -0.5, 0.0]));
+    assert!(locator.locate([1.5, 0.5, 1.1]).is_none());
+}
+
+#[test]
+fn nearest() {
+    let locator = mesh().locator();
+
+    let inside = locator.nearest([0.25, 0.5, 0.75]).unwrap();
+    assert_eq!(Some(inside.clone()), locator.locate([0.25, 0.5, 0.75]));
+
+    // Beyond the face x = 0 of the hexahedron.
+    let outside = locator.nearest([-2.0, 0.5, 0.75]).unwrap();
+    assert_eq!(outside.element(), 1);
+    assert!(close(outside.coordinates(), [-1.0, 0.0, 0.5]));
+
+    // Beyond the tip of the tetrahedron.
+    let tip = locator.nearest([5.0, -1.0, 0.0]).unwrap();
+    assert_eq!(tip.element(), 4);
+    assert!((tip.weights()[3] - 1.0).abs() < 1e-9);
+
+    assert_eq!(
+        Mesh::new(None, mesh::Nodes::new(), mesh::Elements::new())
+            .locator()
+            .nearest([0.0; 3]),
+        None
+    );
+}
@@ -0,0 +1,214 @@
+//! Combination of several meshes into one, welding coincident nodes.
+
+use {
+    crate::{
+        element::{self, Physical},
+        geometry::{self, Point},
+        mesh::{self, Mesh},
+        node,
+    },
+    std::collections::{BTreeSet, HashMap},
+};
+
+#[cfg(test)]
+mod test;
+
+/// Mesh combined from several parts, with maps from the ids of each part to
+/// the combined ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Merged {
+    mesh: Mesh,
+    nodes: Vec<HashMap<node::Id, node::Id>>,
+    elements: Vec<HashMap<element::Id, element::Id>>,
+}
+
+impl Merged {
+    pub fn mesh(&self) -> &Mesh {
+        &self.mesh
+    }
+
+    pub fn into_mesh(self) -> Mesh {
+        self.mesh
+    }
+
+    /// Combined id of each node, one map per part.
+    pub fn nodes(&self) -> &[HashMap<node::Id, node::Id>] {
+        &self.nodes
+    }
+
+    /// Combined id of each element, one map per part.
+    pub fn elements(&self) -> &[HashMap<element::Id, element::Id>] {
+        &self.elements
+    }
+}
+
+impl Mesh {
+    /// Combines `meshes` into a single mesh.
+    ///
+    /// Node and element ids of each part are shifted, where they would clash,
+    /// past the largest id of the previous parts. Nodes closer than
+    /// `tolerance` are then welded into the one with the lowest id, whichever
+    /// part they come from. Physical groups sharing a name are given the same
+    /// tag; unnamed groups keep their tag unless it is taken by a named group.
+    ///
+    /// Returns `None` if an element refers to a node missing from its part, or
+    /// if the shifted ids overflow.
+    pub fn merge(meshes: &[Mesh], tolerance: f64) -> Option<Merged> {
+        let mut welder = Welder::new(tolerance);
+        let mut groups = Groups::default();
+
+        let mut ns = mesh::Nodes::new();
+        let mut es = mesh::Elements::new();
+        let mut node_maps = Vec::with_capacity(meshes.len());
+        let mut element_maps = Vec::with_capacity(meshes.len());
+
+        let mut last_node = None;
+        let mut last_element = None;
+        for mesh in meshes {
+            let ids: BTreeSet<node::Id> = mesh.nodes.keys().copied().collect();
+            let node_offset = offset(last_node, ids.first().copied())?;
+            let mut node_map = HashMap::new();
+            for id in ids.iter() {
+                let node = mesh.nodes[id];
+                let welded = welder.weld(id.checked_add(node_offset)?, node.coordinates());
+                ns.entry(welded).or_insert(node);
+                node_map.insert(*id, welded);
+            }
+
+            let mut element_ids: Vec<element::Id> = mesh.elements.keys().copied().collect();
+            element_ids.sort_unstable();
+            let element_offset = offset(last_element, element_ids.first().copied())?;
+            let mut element_map = HashMap::new();
+            for id in element_ids.iter() {
+                let (physical, elementary, topology) = &mesh.elements[id];
+                let key = (topology.dimension(), *physical);
+                let physical = groups.tag(key, mesh.physical_names.get(&key));
+                let nodes: Option<Vec<node::Id>> = topology
+                    .nodes()
+                    .iter()
+                    .map(|n| node_map.get(n).copied())
+                    .collect();
+                let topology = element::Topology::new(topology.family(), &nodes?)?;
+                let combined = id.checked_add(element_offset)?;
+                es.insert(combined, (physical, *elementary, topology));
+                element_map.insert(*id, combined);
+            }
+
+            if let Some(last) = ids.last() {
+                last_node = Some(last + node_offset);
+            }
+            if let Some(last) = element_ids.last() {
+                last_element = Some(last + element_offset);
+            }
+            node_maps.push(node_map);
+            element_maps.push(element_map);
+        }
+
+        let format = meshes.first().and_then(|m| m.format.clone());
+        Some(Merged {
+            mesh: Mesh::new(format, ns, es).with_physical_names(groups.names),
+            nodes: node_maps,
+            elements: element_maps,
+        })
+    }
+}
+
+/// Shift moving ids from `first` on past `last`, the largest id taken so far,
+/// or 0 when they do not clash.
+fn offset(last: Option<i32>, first: Option<i32>) -> Option<i32> {
+    match (last, first) {
+        (Some(last), Some(first)) if first <= last => last.checked_sub(first)?.checked_add(1),
+        _ => Some(0),
+    }
+}
+
+/// Physical groups of the combined mesh.
+#[derive(Default)]
+struct Groups {
+    names: mesh::PhysicalNames,
+    tags: HashMap<(i32, String), Physical>,
+    used: BTreeSet<(i32, Physical)>,
+    renamed: HashMap<(i32, Physical, Option<String>), Physical>,
+}
+
+impl Groups {
+    fn tag(&mut self, (dimension, tag): (i32, Physical), name: Option<&String>) -> Physical {
+        if tag == 0 && name.is_none() {
+            return 0;
+        }
+        if let Some(tag) = name.and_then(|n| self.tags.get(&(dimension, n.clone()))) {
+            return *tag;
+        }
+        let key = (dimension, tag, name.cloned());
+        if let Some(tag) = self.renamed.get(&key) {
+            return *tag;
+        }
+
+        let taken = self.used.contains(&(dimension, tag));
+        let tag = if taken {
+            let last = self
+                .used
+                .range((dimension, Physical::MIN)..=(dimension, Physical::MAX))
+                .next_back()
+                .map_or(0, |(_, t)| *t);
+            last + 1
+        } else {
+            tag
+        };
+        self.used.insert((dimension, tag));
+        self.renamed.insert(key, tag);
+        if let Some(name) = name {
+            self.tags.insert((dimension, name.clone()), tag);
+            self.names.insert((dimension, tag), name.clone());
+        }
+        tag
+    }
+}
+
+/// Spatial hash mapping points to the first node registered within a
+/// tolerance of them.
+pub(crate) struct Welder {
+    tolerance: f64,
+    cells: HashMap<[i64; 3], Vec<(node::Id, Point)>>,
+}
+
+impl Welder {
+    pub(crate) fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance: tolerance.max(0.0),
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, point: Point) -> [i64; 3] {
+        if self.tolerance > 0.0 {
+            point.map(|x| (x / self.tolerance).floor() as i64)
+        } else {
+            // Adding zero folds -0.0 onto 0.0.
+            point.map(|x| (x + 0.0).to_bits() as i64)
+        }
+    }
+
+    /// Node `id` at `point`, or the earlier node it is welded to.
+    pub(crate) fn weld(&mut self, id: node::Id, point: Point) -> node::Id {
+        let cell = self.cell(point);
+        let reach = if self.tolerance > 0.0 { 1 } else { 0 };
+        for dx in -reach..=reach {
+            for dy in -reach..=reach {
+                for dz in -reach..=reach {
+                    let neighbour = [cell[0] + dx, cell[1] + dy, cell[2] + dz];
+                    let found = self.cells.get(&neighbour).and_then(|nodes| {
+                        nodes
+                            .iter()
+                            .find(|(_, p)| geometry::distance(*p, point) <= self.tolerance)
+                    });
+                    if let Some((welded, _)) = found {
+                        return *welded;
+                    }
+                }
+            }
+        }
+        self.cells.entry(cell).or_default().push((id, point));
+        id
+    }
+}
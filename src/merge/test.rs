@@ -0,0 +1,95 @@
+use crate::{
+    element::Topology,
+    mesh::{self, Mesh},
+    node::Node,
+};
+
+fn square(x: f64, physical: i32, name: &str) -> Mesh {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(x, 0.0, 0.0));
+    ns.insert(2, Node::new(x + 1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(x + 1.0, 1.0, 0.0));
+    ns.insert(4, Node::new(x, 1.0, 0.0));
+
+    let mut es = mesh::Elements::new();
+    es.insert(1, (physical, 1, Topology::Quadrangle4(1, 2, 3, 4)));
+    es.insert(2, (0, 1, Topology::Line2(1, 2)));
+
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((2, physical), name.to_string());
+    Mesh::new(None, ns, es).with_physical_names(names)
+}
+
+#[test]
+fn weld() {
+    let parts = [
+        square(0.0, 5, "steel"),
+        square(1.0 + 1e-9, 7, "steel"),
+        square(2.0, 5, "copper"),
+    ];
+    let merged = Mesh::merge(&parts, 1e-6).unwrap();
+    let mesh = merged.mesh();
+
+    assert_eq!(mesh.nodes().len(), 8);
+    assert_eq!(mesh.elements().len(), 6);
+    assert_eq!(merged.nodes()[1][&1], 2);
+    assert_eq!(merged.nodes()[1][&4], 3);
+    assert_eq!(merged.nodes()[2][&2], 10);
+    assert_eq!(merged.elements()[2][&1], 5);
+
+    assert_eq!(mesh.elements()[&3].2, Topology::Quadrangle4(2, 6, 7, 3));
+    assert_eq!(mesh.elements()[&1].0, 5);
+    assert_eq!(mesh.elements()[&3].0, 5);
+    assert_eq!(mesh.elements()[&5].0, 6);
+    assert_eq!(mesh.elements()[&6].0, 0);
+
+    assert_eq!(mesh.physical_names().len(), 2);
+    assert_eq!(mesh.physical_names()[&(2, 5)], "steel");
+    assert_eq!(mesh.physical_names()[&(2, 6)], "copper");
+}
+
+#[test]
+fn exact() {
+    let parts = [square(0.0, 5, "steel"), square(1.0 + 1e-9, 5, "steel")];
+    let merged = Mesh::merge(&parts, 0.0).unwrap();
+    assert_eq!(merged.mesh().nodes().len(), 8);
+
+    let parts = [square(0.0, 5, "steel"), square(1.0, 5, "steel")];
+    let merged = Mesh::merge(&parts, 0.0).unwrap();
+    assert_eq!(merged.mesh().nodes().len(), 6);
+}
+
+#[test]
+fn ids() {
+    let negative = |x| {
+        let mesh = square(x, 5, "steel");
+        let ns = mesh.nodes().iter().map(|(id, n)| (id - 10, *n)).collect();
+        let es = mesh
+            .elements()
+            .iter()
+            .map(|(id, (p, e, t))| (id - 10, (*p, *e, t.map_nodes(|n| n - 10))))
+            .collect();
+        Mesh::new(None, ns, es)
+    };
+    let parts = [negative(0.0), negative(5.0)];
+    let merged = Mesh::merge(&parts, 0.0).unwrap();
+    assert_eq!(merged.mesh().nodes().len(), 8);
+    assert_eq!(merged.mesh().elements().len(), 4);
+    assert_eq!(merged.nodes()[1][&-9], -5);
+    assert_eq!(merged.elements()[1][&-9], -7);
+
+    let mut last = square(0.0, 5, "steel");
+    last.elements
+        .insert(i32::MAX, (0, 1, Topology::Line2(2, 3)));
+    assert!(Mesh::merge(&[last, square(1.0, 5, "steel")], 0.0).is_none());
+}
+
+#[test]
+fn missing() {
+    let mut broken = square(1.0, 7, "copper");
+    broken
+        .elements
+        .insert(3, (7, 1, Topology::Triangle3(2, 3, 5)));
+    let parts = [square(0.0, 5, "steel"), broken];
+    assert!(Mesh::merge(&parts, 0.0).is_none());
+}
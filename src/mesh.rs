@@ -3,19 +3,54 @@ use {
         decode,
         element::{self, Elementary, Physical, Topology},
         format::Format,
+        geometry::{self, Point},
         node::{self, Node},
     },
     std::{collections::HashMap, io::Read},
 };
 
+#[cfg(all(test, feature = "serde"))]
+mod test;
+
 pub(crate) type Nodes = HashMap<node::Id, Node>;
 pub(crate) type Elements = HashMap<element::Id, (Physical, Elementary, Topology)>;
+/// Names of the physical groups, keyed by their dimension and tag.
+pub(crate) type PhysicalNames = HashMap<(i32, Physical), String>;
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mesh {
-    format: Option<Format>,
-    nodes: Nodes,
-    elements: Elements,
+    pub(crate) format: Option<Format>,
+    pub(crate) nodes: Nodes,
+    pub(crate) elements: Elements,
+    #[cfg_attr(feature = "serde", serde(with = "names"))]
+    pub(crate) physical_names: PhysicalNames,
+}
+
+/// Physical names as a sequence of `((dimension, tag), name)` entries, since
+/// formats such as JSON only take strings as map keys.
+#[cfg(feature = "serde")]
+mod names {
+    use {
+        super::PhysicalNames,
+        serde::{Deserialize, Deserializer, Serializer},
+    };
+
+    pub(super) fn serialize<S: Serializer>(
+        names: &PhysicalNames,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<_> = names.iter().collect();
+        entries.sort_unstable();
+        serializer.collect_seq(entries)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<PhysicalNames, D::Error> {
+        let entries = Vec::<((i32, i32), String)>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
 }
 
 impl Mesh {
@@ -24,16 +59,22 @@ impl Mesh {
             format,
             nodes,
             elements,
+            physical_names: PhysicalNames::new(),
         }
     }
 
+    pub fn with_physical_names(mut self, physical_names: PhysicalNames) -> Self {
+        self.physical_names = physical_names;
+        self
+    }
+
     pub fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
         let mut ss = String::new();
         reader.read_to_string(&mut ss)?;
         match decode::mesh::<nom::error::Error<&str>>(&ss) {
             Ok((_, mesh)) => Ok(mesh),
             Err(_) => {
-                let err = std::io::Error::new(std::io::ErrorKind::Other, "failed to decode mesh");
+                let err = std::io::Error::other("failed to decode mesh");
                 Err(err)
             }
         }
@@ -46,4 +87,24 @@ impl Mesh {
     pub fn elements(&self) -> &Elements {
         &self.elements
     }
+
+    pub fn physical_names(&self) -> &PhysicalNames {
+        &self.physical_names
+    }
+
+    /// Average of the node coordinates of element `id`.
+    pub fn centroid(&self, id: element::Id) -> Option<[f64; 3]> {
+        let (_, _, topology) = self.elements.get(&id)?;
+        let points = self.points(topology)?;
+        Some(geometry::centroid(&points))
+    }
+
+    /// Coordinates of the nodes of `topology`, `None` if any node is missing.
+    pub(crate) fn points(&self, topology: &Topology) -> Option<Vec<Point>> {
+        topology
+            .nodes()
+            .into_iter()
+            .map(|id| self.nodes.get(&id).map(Node::coordinates))
+            .collect()
+    }
 }
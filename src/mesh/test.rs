@@ -0,0 +1,64 @@
+use {
+    crate::{
+        decode,
+        element::Topology,
+        format::Format,
+        mesh::{self, Mesh},
+        node::Node,
+    },
+    nom::error::ErrorKind,
+};
+
+fn square() -> Mesh {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(1.0, 1.0, 0.0));
+    ns.insert(5, Node::new(0.0, 1.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 10, Topology::Triangle3(1, 2, 3)));
+    es.insert(2, (1, 10, Topology::Triangle3(1, 3, 5)));
+    es.insert(7, (2, 20, Topology::Line2(1, 2)));
+    Mesh::new(None, ns, es)
+}
+
+#[test]
+fn serde() {
+    let mut names = mesh::PhysicalNames::new();
+    names.insert((2, 1), String::from("plate"));
+    let mesh = Mesh::new(
+        Some(Format::new(2.2, 0, 8)),
+        square().nodes,
+        square().elements,
+    )
+    .with_physical_names(names);
+    let json = serde_json::to_string(&mesh).unwrap();
+    assert!(json.contains("\"physical_names\":[[[2,1],\"plate\"]]"));
+    assert_eq!(serde_json::from_str::<Mesh>(&json).unwrap(), mesh);
+
+    let text = "$MeshFormat
+4.1 0 8
+$EndMeshFormat
+$Nodes
+1 3 1 3
+2 1 0 3
+1
+2
+3
+0 0 0
+1 0 0
+0 1 0
+$EndNodes
+$Elements
+1 1 1 1
+2 1 2 1
+1 1 2 3
+$EndElements
+";
+    let (_, sections) = decode::v4::mesh::<(&str, ErrorKind)>(text).unwrap();
+    let json = serde_json::to_string(&sections).unwrap();
+    assert_eq!(
+        serde_json::from_str::<decode::v4::Mesh>(&json).unwrap(),
+        sections
+    );
+}
@@ -2,6 +2,7 @@ pub(crate) type Id = i32;
 pub(crate) type Coordinate = f64;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node {
     x: Coordinate,
     y: Coordinate,
@@ -12,4 +13,20 @@ impl Node {
     pub const fn new(x: Coordinate, y: Coordinate, z: Coordinate) -> Self {
         Self { x, y, z }
     }
+
+    pub const fn x(&self) -> Coordinate {
+        self.x
+    }
+
+    pub const fn y(&self) -> Coordinate {
+        self.y
+    }
+
+    pub const fn z(&self) -> Coordinate {
+        self.z
+    }
+
+    pub const fn coordinates(&self) -> [Coordinate; 3] {
+        [self.x, self.y, self.z]
+    }
 }
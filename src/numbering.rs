@@ -0,0 +1,125 @@
+//! Global numbering of edges and faces with orientation, as required by edge
+//! (Nédélec) and face (Raviart–Thomas) finite elements.
+
+use {
+    crate::{element, mesh::Mesh, node},
+    std::collections::HashMap,
+};
+
+#[cfg(test)]
+mod test;
+
+/// Global entity referenced by a local entity of an element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Incidence {
+    index: usize,
+    orientation: i8,
+}
+
+impl Incidence {
+    /// Index of the entity in `Numbering::entities`.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// `1` if the local entity runs like the global one, `-1` otherwise.
+    pub fn orientation(&self) -> i8 {
+        self.orientation
+    }
+}
+
+/// Deduplicated entities and their incidence on each element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Numbering<T> {
+    entities: Vec<T>,
+    incidence: HashMap<element::Id, Vec<Incidence>>,
+}
+
+impl<T> Numbering<T> {
+    pub fn entities(&self) -> &[T] {
+        &self.entities
+    }
+
+    /// Entities of each element, in the local order of `Topology::edges` or
+    /// `Topology::faces`.
+    pub fn incidence(&self) -> &HashMap<element::Id, Vec<Incidence>> {
+        &self.incidence
+    }
+}
+
+pub type Edges = Numbering<[node::Id; 2]>;
+pub type Faces = Numbering<Vec<node::Id>>;
+
+impl Mesh {
+    /// Unique edges, running from the lower to the higher node id.
+    ///
+    /// Edges are numbered in order of appearance, visiting elements by
+    /// increasing id.
+    pub fn edges(&self) -> Edges {
+        self.numbering(
+            |topology| topology.edges(),
+            |&[a, b]| {
+                if a < b {
+                    ([a, b], 1)
+                } else {
+                    ([b, a], -1)
+                }
+            },
+        )
+    }
+
+    /// Unique faces, starting at their lowest node id and running towards its
+    /// lowest neighbour.
+    ///
+    /// Faces of volume elements and surface elements are shared, so that a
+    /// boundary triangle refers to the face of the tetrahedron it lies on.
+    pub fn faces(&self) -> Faces {
+        self.numbering(|topology| topology.faces(), |face| canonical(face))
+    }
+
+    fn numbering<T, L, F, C>(&self, local: F, canonical: C) -> Numbering<T>
+    where
+        T: Clone + Eq + std::hash::Hash,
+        F: Fn(&element::Topology) -> Vec<L>,
+        C: Fn(&L) -> (T, i8),
+    {
+        let mut ids: Vec<&element::Id> = self.elements.keys().collect();
+        ids.sort_unstable();
+
+        let mut indices: HashMap<T, usize> = HashMap::new();
+        let mut entities = Vec::new();
+        let mut incidence = HashMap::new();
+        for id in ids {
+            let (_, _, topology) = &self.elements[id];
+            let oriented = local(topology)
+                .iter()
+                .map(|entity| {
+                    let (entity, orientation) = canonical(entity);
+                    let index = *indices.entry(entity.clone()).or_insert_with(|| {
+                        entities.push(entity);
+                        entities.len() - 1
+                    });
+                    Incidence { index, orientation }
+                })
+                .collect();
+            incidence.insert(*id, oriented);
+        }
+
+        Numbering {
+            entities,
+            incidence,
+        }
+    }
+}
+
+fn canonical(face: &[node::Id]) -> (Vec<node::Id>, i8) {
+    let n = face.len();
+    let first = (0..n).min_by_key(|&i| face[i]).unwrap_or(0);
+    let next = face[(first + 1) % n];
+    let previous = face[(first + n - 1) % n];
+    if next < previous {
+        ((0..n).map(|i| face[(first + i) % n]).collect(), 1)
+    } else {
+        ((0..n).map(|i| face[(first + n - i) % n]).collect(), -1)
+    }
+}
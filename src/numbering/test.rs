@@ -0,0 +1,59 @@
+use crate::{
+    element::Topology,
+    mesh::{self, Mesh},
+    node::Node,
+};
+
+fn mesh() -> Mesh {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(0.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 0.0, 1.0));
+    ns.insert(5, Node::new(1.0, 1.0, 1.0));
+
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Tetrahedron4(1, 2, 3, 4)));
+    es.insert(2, (1, 1, Topology::Tetrahedron4(2, 3, 4, 5)));
+    es.insert(3, (2, 2, Topology::Triangle3(3, 2, 1)));
+
+    Mesh::new(None, ns, es)
+}
+
+#[test]
+fn edges() {
+    let edges = mesh().edges();
+
+    assert_eq!(edges.entities().len(), 9);
+    assert_eq!(&edges.entities()[..3], &[[1, 2], [2, 3], [1, 3]]);
+
+    let first = &edges.incidence()[&1];
+    let orientations: Vec<i8> = first.iter().map(|i| i.orientation()).collect();
+    assert_eq!(orientations, vec![1, 1, -1, -1, -1, -1]);
+
+    let triangle = &edges.incidence()[&3];
+    let indices: Vec<usize> = triangle.iter().map(|i| i.index()).collect();
+    assert_eq!(indices, vec![1, 0, 2]);
+    let orientations: Vec<i8> = triangle.iter().map(|i| i.orientation()).collect();
+    assert_eq!(orientations, vec![-1, -1, 1]);
+}
+
+#[test]
+fn faces() {
+    let faces = mesh().faces();
+
+    assert_eq!(faces.entities().len(), 7);
+    assert_eq!(faces.entities()[0], vec![1, 2, 3]);
+
+    // Face (2, 3, 4) is shared with opposite orientations.
+    let shared = faces.incidence()[&1][3];
+    let other = faces.incidence()[&2][0];
+    assert_eq!(shared.index(), other.index());
+    assert_eq!(shared.orientation(), -other.orientation());
+
+    // The boundary triangle refers to the bottom face of the first element.
+    let bottom = faces.incidence()[&1][0];
+    let triangle = faces.incidence()[&3][0];
+    assert_eq!(bottom, triangle);
+    assert_eq!(bottom.orientation(), -1);
+}
@@ -0,0 +1,142 @@
+//! Gauss quadrature rules on the gmsh reference elements.
+//!
+//! Lines, quadrangles and hexahedra use tensor products of Gauss-Legendre
+//! rules; simplices, prisms and pyramids collapse such products onto the
+//! reference element.
+
+use {
+    crate::{element::Family, geometry::Point},
+    std::f64::consts::PI,
+};
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quadrature {
+    points: Vec<Point>,
+    weights: Vec<f64>,
+}
+
+impl Quadrature {
+    /// Rule integrating polynomials of degree up to `order` exactly over the
+    /// reference element of `family`.
+    pub fn new(family: Family, order: usize) -> Self {
+        let line = |order: usize| legendre(order / 2 + 1);
+        // Rule over [0, 1].
+        let unit = |order: usize| -> Vec<(f64, f64)> {
+            line(order)
+                .into_iter()
+                .map(|(x, w)| ((1.0 + x) / 2.0, w / 2.0))
+                .collect()
+        };
+
+        let mut rule = Self {
+            points: Vec::new(),
+            weights: Vec::new(),
+        };
+        match family {
+            Family::Point => rule.push([0.0; 3], 1.0),
+            Family::Line => {
+                for (x, w) in line(order) {
+                    rule.push([x, 0.0, 0.0], w);
+                }
+            }
+            Family::Quadrangle => {
+                let line = line(order);
+                for &(y, wy) in &line {
+                    for &(x, wx) in &line {
+                        rule.push([x, y, 0.0], wx * wy);
+                    }
+                }
+            }
+            Family::Hexahedron => {
+                let line = line(order);
+                for &(z, wz) in &line {
+                    for &(y, wy) in &line {
+                        for &(x, wx) in &line {
+                            rule.push([x, y, z], wx * wy * wz);
+                        }
+                    }
+                }
+            }
+            Family::Triangle | Family::Prism => {
+                let extrusion = match family {
+                    Family::Prism => line(order),
+                    _ => vec![(0.0, 1.0)],
+                };
+                for (z, wz) in extrusion {
+                    for (t, wt) in unit(order + 1) {
+                        for (s, ws) in unit(order) {
+                            let p = [s * (1.0 - t), t, z];
+                            rule.push(p, ws * wt * wz * (1.0 - t));
+                        }
+                    }
+                }
+            }
+            Family::Tetrahedron => {
+                for (r, wr) in unit(order + 2) {
+                    for (t, wt) in unit(order + 1) {
+                        for (s, ws) in unit(order) {
+                            let p = [s * (1.0 - t) * (1.0 - r), t * (1.0 - r), r];
+                            let jacobian = (1.0 - t) * (1.0 - r).powi(2);
+                            rule.push(p, ws * wt * wr * jacobian);
+                        }
+                    }
+                }
+            }
+            Family::Pyramid => {
+                let line = line(order);
+                for (r, wr) in unit(order + 2) {
+                    let scale = 1.0 - r;
+                    for &(y, wy) in &line {
+                        for &(x, wx) in &line {
+                            let p = [x * scale, y * scale, r];
+                            rule.push(p, wx * wy * wr * scale * scale);
+                        }
+                    }
+                }
+            }
+        }
+        rule
+    }
+
+    /// Reference coordinates of the quadrature points.
+    pub fn points(&self) -> &[[f64; 3]] {
+        &self.points
+    }
+
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    fn push(&mut self, point: Point, weight: f64) {
+        self.points.push(point);
+        self.weights.push(weight);
+    }
+}
+
+/// Points and weights of the Gauss-Legendre rule with `n` points over
+/// [-1, 1], by Newton iterations on the Legendre polynomial.
+fn legendre(n: usize) -> Vec<(f64, f64)> {
+    let mut rule = Vec::with_capacity(n);
+    for i in 0..n {
+        let mut x = (PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+        let mut derivative = 0.0;
+        for _ in 0..100 {
+            let (mut p0, mut p1) = (1.0, x);
+            for k in 2..=n {
+                let k = k as f64;
+                (p0, p1) = (p1, ((2.0 * k - 1.0) * x * p1 - (k - 1.0) * p0) / k);
+            }
+            derivative = n as f64 * (x * p1 - p0) / (x * x - 1.0);
+            let step = p1 / derivative;
+            x -= step;
+            if step.abs() < 1e-15 {
+                break;
+            }
+        }
+        rule.push((x, 2.0 / ((1.0 - x * x) * derivative * derivative)));
+    }
+    rule
+}
@@ -0,0 +1,80 @@
+use crate::{element::Family, quadrature::Quadrature};
+
+fn factorial(n: i32) -> f64 {
+    (1..=n).map(f64::from).product()
+}
+
+/// Exact integral of `u^i v^j w^k` over the reference element.
+fn exact(family: Family, [i, j, k]: [i32; 3]) -> f64 {
+    // Integral of x^n over [-1, 1].
+    let line = |n: i32| {
+        if n % 2 == 0 {
+            2.0 / (n + 1) as f64
+        } else {
+            0.0
+        }
+    };
+    match family {
+        Family::Line => line(i),
+        Family::Quadrangle => line(i) * line(j),
+        Family::Hexahedron => line(i) * line(j) * line(k),
+        Family::Triangle => factorial(i) * factorial(j) / factorial(i + j + 2),
+        Family::Tetrahedron => {
+            factorial(i) * factorial(j) * factorial(k) / factorial(i + j + k + 3)
+        }
+        Family::Prism => factorial(i) * factorial(j) / factorial(i + j + 2) * line(k),
+        // Square sections of half width 1 - w.
+        Family::Pyramid => {
+            let a = line(i) * line(j);
+            let n = i + j + 2;
+            // Integral of w^k (1 - w)^n over [0, 1].
+            a * factorial(k) * factorial(n) / factorial(k + n + 1)
+        }
+        Family::Point => 1.0,
+    }
+}
+
+#[test]
+fn exactness() {
+    let families = [
+        Family::Line,
+        Family::Triangle,
+        Family::Quadrangle,
+        Family::Tetrahedron,
+        Family::Hexahedron,
+        Family::Prism,
+        Family::Pyramid,
+    ];
+    for family in families {
+        for order in 0..=6 {
+            let rule = Quadrature::new(family, order);
+            assert_eq!(rule.points().len(), rule.weights().len());
+            for degree in 0..=order as i32 {
+                for i in 0..=degree {
+                    for j in 0..=degree - i {
+                        let k = degree - i - j;
+                        let powers = [i, j, k];
+                        let dimension = family.dimension() as usize;
+                        if powers[dimension..].iter().any(|&p| p > 0) {
+                            continue;
+                        }
+                        let integral: f64 = rule
+                            .points()
+                            .iter()
+                            .zip(rule.weights())
+                            .map(|(p, w)| w * p[0].powi(i) * p[1].powi(j) * p[2].powi(k))
+                            .sum();
+                        let expected = exact(family, powers);
+                        assert!(
+                            (integral - expected).abs() < 1e-12,
+                            "{family:?} {order} {powers:?}: {integral} != {expected}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    let point = Quadrature::new(Family::Point, 3);
+    assert_eq!(point.weights(), &[1.0]);
+}
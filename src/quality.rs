@@ -0,0 +1,622 @@
+//! Element quality metrics, summarised per physical group.
+//!
+//! Corner based metrics (scaled Jacobian, SICN and the gamma of non-simplicial
+//! elements) are normalised against an ideal element of the same family, so
+//! that equilateral simplices, squares and cubes score 1.
+
+use {
+    crate::{
+        element::{self, Family, Physical},
+        geometry::{self, Point},
+        mesh::Mesh,
+    },
+    std::collections::HashMap,
+};
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// Longest over shortest edge, infinite if an edge is collapsed.
+    AspectRatio,
+    /// Equiangular skewness over the element faces.
+    Skewness,
+    /// Smallest dihedral angle (interior angle for surface elements), in degrees.
+    MinimumAngle,
+    /// Largest dihedral angle (interior angle for surface elements), in degrees.
+    MaximumAngle,
+    /// Smallest normalised corner Jacobian determinant.
+    ScaledJacobian,
+    /// Inscribed over circumscribed radius, as reported by gmsh.
+    Gamma,
+    /// Signed inverse condition number, as reported by gmsh.
+    Sicn,
+}
+
+impl Metric {
+    /// Range the metric is bounded to, `None` if it is unbounded.
+    pub const fn range(&self) -> Option<(f64, f64)> {
+        match self {
+            Self::AspectRatio => None,
+            Self::Skewness | Self::Gamma => Some((0.0, 1.0)),
+            Self::MinimumAngle | Self::MaximumAngle => Some((0.0, 180.0)),
+            Self::ScaledJacobian | Self::Sicn => Some((-1.0, 1.0)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quality {
+    aspect_ratio: f64,
+    skewness: f64,
+    minimum_angle: f64,
+    maximum_angle: f64,
+    scaled_jacobian: f64,
+    gamma: f64,
+    sicn: f64,
+}
+
+impl Quality {
+    /// Quality of an element of the given `family` with vertices `points`.
+    ///
+    /// Only surface and volume elements have a quality.
+    pub(crate) fn new(family: Family, points: &[Point]) -> Option<Self> {
+        if family.dimension() < 2 || points.len() < family.vertices() {
+            return None;
+        }
+        let points = &points[..family.vertices()];
+        let normal = normal(family, points);
+
+        let lengths: Vec<f64> = family
+            .edges()
+            .iter()
+            .map(|&[a, b]| geometry::distance(points[a], points[b]))
+            .collect();
+        // Collapsed edges make the aspect ratio infinite rather than NaN.
+        let aspect_ratio = match minimum(&lengths) {
+            shortest if shortest > 0.0 => maximum(&lengths) / shortest,
+            _ => f64::INFINITY,
+        };
+
+        let angles = angles(family, points);
+        let corners = corners(family);
+        let ideal = ideal(family);
+
+        let mut scaled_jacobian = f64::INFINITY;
+        let mut sicn = f64::INFINITY;
+        let mut gamma = f64::INFINITY;
+        for (vertex, neighbours) in corners.iter() {
+            let actual = edges(points, *vertex, neighbours);
+            let reference = edges(&ideal, *vertex, neighbours);
+
+            scaled_jacobian = scaled_jacobian.min(scaled(&actual, normal) / scaled(&reference, Z));
+            sicn = sicn.min(inverse_condition(&actual, normal, &reference));
+            if !simplex(family) {
+                gamma = gamma.min(corner_gamma(&actual) / corner_gamma(&reference));
+            }
+        }
+        if simplex(family) {
+            gamma = simplex_gamma(points);
+        }
+
+        Some(Self {
+            aspect_ratio,
+            skewness: skewness(family, points),
+            minimum_angle: minimum(&angles),
+            maximum_angle: maximum(&angles),
+            scaled_jacobian,
+            gamma,
+            sicn,
+        })
+    }
+
+    pub fn get(&self, metric: Metric) -> f64 {
+        match metric {
+            Metric::AspectRatio => self.aspect_ratio,
+            Metric::Skewness => self.skewness,
+            Metric::MinimumAngle => self.minimum_angle,
+            Metric::MaximumAngle => self.maximum_angle,
+            Metric::ScaledJacobian => self.scaled_jacobian,
+            Metric::Gamma => self.gamma,
+            Metric::Sicn => self.sicn,
+        }
+    }
+
+    pub fn aspect_ratio(&self) -> f64 {
+        self.aspect_ratio
+    }
+
+    pub fn skewness(&self) -> f64 {
+        self.skewness
+    }
+
+    pub fn minimum_angle(&self) -> f64 {
+        self.minimum_angle
+    }
+
+    pub fn maximum_angle(&self) -> f64 {
+        self.maximum_angle
+    }
+
+    pub fn scaled_jacobian(&self) -> f64 {
+        self.scaled_jacobian
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    pub fn sicn(&self) -> f64 {
+        self.sicn
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Statistics {
+    count: usize,
+    minimum: f64,
+    maximum: f64,
+    mean: f64,
+}
+
+impl Statistics {
+    /// Summary of `values`, `None` if there are none.
+    pub fn new<I: IntoIterator<Item = f64>>(values: I) -> Option<Self> {
+        let mut count = 0;
+        let mut minimum = f64::INFINITY;
+        let mut maximum = f64::NEG_INFINITY;
+        let mut sum = 0.0;
+        for value in values {
+            count += 1;
+            minimum = minimum.min(value);
+            maximum = maximum.max(value);
+            sum += value;
+        }
+        if count == 0 {
+            return None;
+        }
+        Some(Self {
+            count,
+            minimum,
+            maximum,
+            mean: sum / count as f64,
+        })
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn minimum(&self) -> f64 {
+        self.minimum
+    }
+
+    pub fn maximum(&self) -> f64 {
+        self.maximum
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    lower: f64,
+    upper: f64,
+    counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Histogram of `values` over `bins` equal bins spanning `[lower, upper]`.
+    ///
+    /// Values outside the range are accumulated in the first or last bin.
+    pub fn new<I: IntoIterator<Item = f64>>(
+        values: I,
+        lower: f64,
+        upper: f64,
+        bins: usize,
+    ) -> Self {
+        let mut counts = vec![0; bins.max(1)];
+        let width = (upper - lower) / counts.len() as f64;
+        for value in values {
+            let bin = if width > 0.0 {
+                ((value - lower) / width).floor().max(0.0) as usize
+            } else {
+                0
+            };
+            let last = counts.len() - 1;
+            counts[bin.min(last)] += 1;
+        }
+        Self {
+            lower,
+            upper,
+            counts,
+        }
+    }
+
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+}
+
+impl Mesh {
+    /// Quality of every surface and volume element.
+    pub fn quality(&self) -> HashMap<element::Id, Quality> {
+        self.elements
+            .iter()
+            .filter_map(|(id, (_, _, topology))| {
+                let points = self.points(topology)?;
+                Quality::new(topology.family(), &points).map(|q| (*id, q))
+            })
+            .collect()
+    }
+
+    /// Statistics of `metric` for each physical group.
+    pub fn quality_statistics(&self, metric: Metric) -> HashMap<Physical, Statistics> {
+        self.quality_values(metric)
+            .into_iter()
+            .filter_map(|(physical, values)| Statistics::new(values).map(|s| (physical, s)))
+            .collect()
+    }
+
+    /// Histogram of `metric` for each physical group.
+    ///
+    /// Bounded metrics use their natural range; the aspect ratio spans from 1
+    /// up to its largest finite value in the mesh, so that groups stay
+    /// comparable, with collapsed elements in the last bin.
+    pub fn quality_histogram(&self, metric: Metric, bins: usize) -> HashMap<Physical, Histogram> {
+        let values = self.quality_values(metric);
+        let (lower, upper) = metric.range().unwrap_or_else(|| {
+            let finite = values.values().flatten().filter(|v| v.is_finite());
+            let upper = finite.fold(1.0, |a: f64, &b| a.max(b));
+            (1.0, upper)
+        });
+        values
+            .into_iter()
+            .map(|(physical, values)| (physical, Histogram::new(values, lower, upper, bins)))
+            .collect()
+    }
+
+    fn quality_values(&self, metric: Metric) -> HashMap<Physical, Vec<f64>> {
+        let quality = self.quality();
+        let mut values: HashMap<Physical, Vec<f64>> = HashMap::new();
+        for (id, (physical, _, _)) in self.elements.iter() {
+            if let Some(q) = quality.get(id) {
+                values.entry(*physical).or_default().push(q.get(metric));
+            }
+        }
+        values
+    }
+}
+
+const Z: Point = [0.0, 0.0, 1.0];
+
+fn simplex(family: Family) -> bool {
+    matches!(family, Family::Triangle | Family::Tetrahedron)
+}
+
+fn minimum(values: &[f64]) -> f64 {
+    values.iter().fold(f64::INFINITY, |a, &b| a.min(b))
+}
+
+fn maximum(values: &[f64]) -> f64 {
+    values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b))
+}
+
+/// Vertices of the ideal element of `family`: equilateral simplices, unit
+/// square and cube, and unit prism and pyramid with equal edges.
+fn ideal(family: Family) -> Vec<Point> {
+    let h = 3f64.sqrt() / 2.0;
+    match family {
+        Family::Point => vec![[0.0, 0.0, 0.0]],
+        Family::Line => vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+        Family::Triangle => vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.5, h, 0.0]],
+        Family::Quadrangle => vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+        ],
+        Family::Tetrahedron => vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.5, h, 0.0],
+            [0.5, h / 3.0, (2.0f64 / 3.0).sqrt()],
+        ],
+        Family::Hexahedron => vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [0.0, 1.0, 1.0],
+        ],
+        Family::Prism => vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.5, h, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [0.5, h, 1.0],
+        ],
+        Family::Pyramid => vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.5, 0.5, 0.5f64.sqrt()],
+        ],
+    }
+}
+
+/// Vertices with exactly `dimension` incident edges, together with their
+/// neighbours ordered so that the ideal element has a positive Jacobian.
+fn corners(family: Family) -> Vec<(usize, Vec<usize>)> {
+    let ideal = ideal(family);
+    let dimension = family.dimension() as usize;
+    (0..family.vertices())
+        .filter_map(|vertex| {
+            let mut neighbours: Vec<usize> = family
+                .edges()
+                .iter()
+                .filter_map(|&[a, b]| match (a == vertex, b == vertex) {
+                    (true, _) => Some(b),
+                    (_, true) => Some(a),
+                    _ => None,
+                })
+                .collect();
+            if neighbours.len() != dimension {
+                return None;
+            }
+            if jacobian(&edges(&ideal, vertex, &neighbours), Z) < 0.0 {
+                neighbours.swap(0, 1);
+            }
+            Some((vertex, neighbours))
+        })
+        .collect()
+}
+
+fn edges(points: &[Point], vertex: usize, neighbours: &[usize]) -> Vec<Point> {
+    neighbours
+        .iter()
+        .map(|&n| geometry::sub(points[n], points[vertex]))
+        .collect()
+}
+
+/// Orientation of surface elements: planar elements in the xy plane are
+/// measured against +z so that clockwise elements are reported as inverted.
+fn normal(family: Family, points: &[Point]) -> Point {
+    if family.dimension() != 2 {
+        return Z;
+    }
+    let n = geometry::newell(points);
+    let length = geometry::norm(n);
+    if length == 0.0 || n[0].hypot(n[1]) <= 1e-12 * length {
+        Z
+    } else {
+        geometry::scale(n, 1.0 / length)
+    }
+}
+
+fn jacobian(edges: &[Point], normal: Point) -> f64 {
+    match edges {
+        [e0, e1] => geometry::det(*e0, *e1, normal),
+        [e0, e1, e2] => geometry::det(*e0, *e1, *e2),
+        _ => 0.0,
+    }
+}
+
+fn scaled(edges: &[Point], normal: Point) -> f64 {
+    let lengths: f64 = edges.iter().map(|&e| geometry::norm(e)).product();
+    if lengths == 0.0 {
+        return 0.0;
+    }
+    jacobian(edges, normal) / lengths
+}
+
+/// Inverse condition number of the corner Jacobian mapped from the ideal
+/// corner, signed by its determinant.
+fn inverse_condition(actual: &[Point], normal: Point, ideal: &[Point]) -> f64 {
+    match (actual, ideal) {
+        ([e0, e1], [w0, w1]) => {
+            let e = planar(*e0, *e1, normal);
+            let w = planar(*w0, *w1, Z);
+            let det_w = w[0][0] * w[1][1] - w[1][0] * w[0][1];
+            // A = E W^-1, with columns stored first.
+            let inverse = [[w[1][1], -w[0][1]], [-w[1][0], w[0][0]]];
+            let a: Vec<[f64; 2]> = (0..2)
+                .map(|c| {
+                    [
+                        (e[0][0] * inverse[c][0] + e[1][0] * inverse[c][1]) / det_w,
+                        (e[0][1] * inverse[c][0] + e[1][1] * inverse[c][1]) / det_w,
+                    ]
+                })
+                .collect();
+            let det = a[0][0] * a[1][1] - a[1][0] * a[0][1];
+            let frobenius = a.iter().flatten().map(|x| x * x).sum::<f64>();
+            if frobenius == 0.0 {
+                0.0
+            } else {
+                2.0 * det / frobenius
+            }
+        }
+        ([e0, e1, e2], [w0, w1, w2]) => {
+            let det_w = geometry::det(*w0, *w1, *w2);
+            let rows = [
+                geometry::cross(*w1, *w2),
+                geometry::cross(*w2, *w0),
+                geometry::cross(*w0, *w1),
+            ];
+            let e = [*e0, *e1, *e2];
+            let a: Vec<Point> = (0..3)
+                .map(|c| {
+                    (0..3).fold([0.0; 3], |acc, i| {
+                        geometry::add(acc, geometry::scale(e[i], rows[i][c] / det_w))
+                    })
+                })
+                .collect();
+            let det = geometry::det(a[0], a[1], a[2]);
+            let frobenius = a.iter().map(|&c| geometry::dot(c, c)).sum::<f64>().sqrt();
+            let adjugate = [
+                geometry::cross(a[1], a[2]),
+                geometry::cross(a[2], a[0]),
+                geometry::cross(a[0], a[1]),
+            ]
+            .iter()
+            .map(|&c| geometry::dot(c, c))
+            .sum::<f64>()
+            .sqrt();
+            if frobenius == 0.0 || adjugate == 0.0 {
+                0.0
+            } else {
+                3.0 * det / (frobenius * adjugate)
+            }
+        }
+        _ => 0.0,
+    }
+}
+
+/// In-plane coordinates of two edges, in a frame aligned with the first one.
+fn planar(e0: Point, e1: Point, normal: Point) -> [[f64; 2]; 2] {
+    let projected = geometry::sub(e0, geometry::scale(normal, geometry::dot(e0, normal)));
+    let length = geometry::norm(projected);
+    if length == 0.0 {
+        return [[0.0; 2]; 2];
+    }
+    let t1 = geometry::scale(projected, 1.0 / length);
+    let t2 = geometry::cross(normal, t1);
+    [
+        [geometry::dot(e0, t1), geometry::dot(e0, t2)],
+        [geometry::dot(e1, t1), geometry::dot(e1, t2)],
+    ]
+}
+
+/// Interior angles of surface elements, dihedral angles of volume elements.
+fn angles(family: Family, points: &[Point]) -> Vec<f64> {
+    if family.dimension() == 2 {
+        return corners(family)
+            .iter()
+            .map(|(vertex, neighbours)| {
+                let e = edges(points, *vertex, neighbours);
+                geometry::angle(e[0], e[1]).to_degrees()
+            })
+            .collect();
+    }
+
+    let normals: Vec<Point> = family
+        .faces()
+        .iter()
+        .map(|face| {
+            let vertices: Vec<Point> = face.iter().map(|&v| points[v]).collect();
+            geometry::newell(&vertices)
+        })
+        .collect();
+    family
+        .edges()
+        .iter()
+        .filter_map(|&[a, b]| {
+            let mut adjacent = family.faces().iter().enumerate().filter(|(_, face)| {
+                (0..face.len()).any(|i| {
+                    let (p, q) = (face[i], face[(i + 1) % face.len()]);
+                    (p, q) == (a, b) || (p, q) == (b, a)
+                })
+            });
+            let (f1, _) = adjacent.next()?;
+            let (f2, _) = adjacent.next()?;
+            let angle = geometry::angle(normals[f1], normals[f2]);
+            Some(180.0 - angle.to_degrees())
+        })
+        .collect()
+}
+
+fn skewness(family: Family, points: &[Point]) -> f64 {
+    family
+        .faces()
+        .iter()
+        .map(|face| {
+            let equiangular = 180.0 * (face.len() - 2) as f64 / face.len() as f64;
+            let angles: Vec<f64> = (0..face.len())
+                .map(|i| {
+                    let vertex = points[face[i]];
+                    let previous = points[face[(i + face.len() - 1) % face.len()]];
+                    let next = points[face[(i + 1) % face.len()]];
+                    geometry::angle(geometry::sub(previous, vertex), geometry::sub(next, vertex))
+                        .to_degrees()
+                })
+                .collect();
+            let over = (maximum(&angles) - equiangular) / (180.0 - equiangular);
+            let under = (equiangular - minimum(&angles)) / equiangular;
+            over.max(under)
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Gamma of a triangle or tetrahedron given by its vertices.
+fn simplex_gamma(points: &[Point]) -> f64 {
+    match points {
+        [p0, p1, p2] => {
+            let area = geometry::norm(geometry::cross(
+                geometry::sub(*p1, *p0),
+                geometry::sub(*p2, *p0),
+            )) / 2.0;
+            let a = geometry::distance(*p0, *p1);
+            let b = geometry::distance(*p1, *p2);
+            let c = geometry::distance(*p2, *p0);
+            let s = (a + b + c) / 2.0;
+            let denominator = s * a * b * c;
+            if denominator == 0.0 {
+                0.0
+            } else {
+                8.0 * area * area / denominator
+            }
+        }
+        [p0, p1, p2, p3] => {
+            let a = geometry::sub(*p1, *p0);
+            let b = geometry::sub(*p2, *p0);
+            let c = geometry::sub(*p3, *p0);
+            let det = geometry::det(a, b, c);
+            let area = |x: Point, y: Point, z: Point| {
+                geometry::norm(geometry::cross(geometry::sub(y, x), geometry::sub(z, x))) / 2.0
+            };
+            let surface = area(*p0, *p1, *p2)
+                + area(*p0, *p1, *p3)
+                + area(*p0, *p2, *p3)
+                + area(*p1, *p2, *p3);
+            if det == 0.0 || surface == 0.0 {
+                return 0.0;
+            }
+            let inscribed = det.abs() / 2.0 / surface;
+            let centre = geometry::scale(
+                geometry::add(
+                    geometry::add(
+                        geometry::scale(geometry::cross(b, c), geometry::dot(a, a)),
+                        geometry::scale(geometry::cross(c, a), geometry::dot(b, b)),
+                    ),
+                    geometry::scale(geometry::cross(a, b), geometry::dot(c, c)),
+                ),
+                1.0 / (2.0 * det),
+            );
+            3.0 * inscribed / geometry::norm(centre)
+        }
+        _ => 0.0,
+    }
+}
+
+/// Gamma of the simplex spanned by a corner and its neighbours.
+fn corner_gamma(edges: &[Point]) -> f64 {
+    let mut points = vec![[0.0; 3]];
+    points.extend_from_slice(edges);
+    simplex_gamma(&points)
+}
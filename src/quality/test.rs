@@ -0,0 +1,129 @@
+use {
+    super::Metric,
+    crate::{
+        element::Topology,
+        mesh::{self, Mesh},
+        node::Node,
+    },
+};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn ideal() {
+    let h = 3f64.sqrt() / 2.0;
+
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(1.0, 0.0, 0.0));
+    ns.insert(3, Node::new(0.5, h, 0.0));
+    ns.insert(4, Node::new(0.5, h / 3.0, (2.0f64 / 3.0).sqrt()));
+    ns.insert(5, Node::new(1.0, 1.0, 0.0));
+    ns.insert(6, Node::new(0.0, 1.0, 0.0));
+    ns.insert(7, Node::new(0.0, 0.0, 1.0));
+    ns.insert(8, Node::new(1.0, 0.0, 1.0));
+    ns.insert(9, Node::new(1.0, 1.0, 1.0));
+    ns.insert(10, Node::new(0.0, 1.0, 1.0));
+    ns.insert(11, Node::new(0.5, h, 1.0));
+    ns.insert(12, Node::new(0.5, 0.5, 0.5f64.sqrt()));
+
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Triangle3(1, 2, 3)));
+    es.insert(2, (1, 1, Topology::Quadrangle4(1, 2, 5, 6)));
+    es.insert(3, (2, 2, Topology::Tetrahedron4(1, 2, 3, 4)));
+    es.insert(4, (2, 2, Topology::Hexahedron8(1, 2, 5, 6, 7, 8, 9, 10)));
+    es.insert(5, (0, 3, Topology::Line2(1, 2)));
+    es.insert(6, (3, 3, Topology::Prism6(1, 2, 3, 7, 8, 11)));
+    es.insert(7, (3, 3, Topology::Pyramid5(1, 2, 5, 6, 12)));
+
+    let quality = Mesh::new(None, ns, es).quality();
+    assert_eq!(quality.len(), 6);
+
+    for q in quality.values() {
+        assert!(close(q.aspect_ratio(), 1.0));
+        assert!(close(q.skewness(), 0.0));
+        assert!(close(q.scaled_jacobian(), 1.0));
+        assert!(close(q.gamma(), 1.0));
+        assert!(close(q.sicn(), 1.0));
+    }
+
+    assert!(close(quality[&1].minimum_angle(), 60.0));
+    assert!(close(quality[&2].maximum_angle(), 90.0));
+    assert!(close(
+        quality[&3].minimum_angle(),
+        (1.0f64 / 3.0).acos().to_degrees()
+    ));
+    assert!(close(quality[&4].minimum_angle(), 90.0));
+    assert!(close(quality[&6].minimum_angle(), 60.0));
+    assert!(close(quality[&6].maximum_angle(), 90.0));
+    assert!(close(
+        quality[&7].minimum_angle(),
+        (1.0f64 / 3.0).sqrt().acos().to_degrees()
+    ));
+}
+
+#[test]
+fn inverted() {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(2.0, 0.0, 0.0));
+    ns.insert(3, Node::new(0.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 0.0, 1.0));
+
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Triangle3(1, 3, 2)));
+    es.insert(2, (2, 2, Topology::Tetrahedron4(1, 3, 2, 4)));
+    es.insert(3, (2, 2, Topology::Tetrahedron4(1, 2, 3, 4)));
+
+    let mesh = Mesh::new(None, ns, es);
+    let quality = mesh.quality();
+    assert!(quality[&1].scaled_jacobian() < 0.0);
+    assert!(quality[&1].sicn() < 0.0);
+    assert!(quality[&2].scaled_jacobian() < 0.0);
+    assert!(quality[&3].scaled_jacobian() > 0.0);
+    assert!(close(quality[&3].aspect_ratio(), 5f64.sqrt()));
+
+    let statistics = mesh.quality_statistics(Metric::ScaledJacobian);
+    assert_eq!(statistics[&2].count(), 2);
+    assert!(statistics[&2].minimum() < 0.0);
+
+    let histogram = mesh.quality_histogram(Metric::ScaledJacobian, 2);
+    assert_eq!(histogram[&1].counts(), &[1, 0]);
+    assert_eq!(histogram[&2].counts(), &[1, 1]);
+}
+
+#[test]
+fn degenerate() {
+    // A tetrahedron with a collapsed edge and a flat one, both of zero volume.
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(2.0, 0.0, 0.0));
+    ns.insert(3, Node::new(0.0, 1.0, 0.0));
+    ns.insert(4, Node::new(0.0, 0.0, 1.0));
+    ns.insert(5, Node::new(1.0, 1.0, 0.0));
+
+    let mut es = mesh::Elements::new();
+    es.insert(1, (1, 1, Topology::Tetrahedron4(1, 2, 3, 4)));
+    es.insert(2, (1, 1, Topology::Tetrahedron4(1, 2, 3, 1)));
+    es.insert(3, (1, 1, Topology::Tetrahedron4(1, 2, 3, 5)));
+
+    let mesh = Mesh::new(None, ns, es);
+    let quality = mesh.quality();
+    assert_eq!(quality[&2].aspect_ratio(), f64::INFINITY);
+    assert!(close(quality[&3].aspect_ratio(), 5f64.sqrt()));
+    for id in [2, 3] {
+        let q = quality[&id];
+        assert_eq!(q.scaled_jacobian(), 0.0);
+        assert_eq!(q.sicn(), 0.0);
+        assert_eq!(q.gamma(), 0.0);
+        assert!(!q.skewness().is_nan() && !q.minimum_angle().is_nan());
+    }
+
+    // The aspect ratio histogram spans the finite values only, with the
+    // collapsed element in the last bin.
+    let histogram = mesh.quality_histogram(Metric::AspectRatio, 2);
+    assert!(close(histogram[&1].upper(), 5f64.sqrt()));
+    assert_eq!(histogram[&1].counts(), &[0, 3]);
+}
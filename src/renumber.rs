@@ -0,0 +1,356 @@
+//! Node reorderings, contiguous renumbering and id compaction of meshes.
+
+use {
+    crate::{
+        adjacency::Adjacency,
+        element,
+        mesh::{self, Mesh},
+        node,
+    },
+    std::collections::{HashMap, HashSet, VecDeque},
+};
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Reordering {
+    /// Increasing id.
+    Natural,
+    /// Breadth first traversal of the node graph from a pseudo-peripheral
+    /// node, visiting neighbours by increasing degree.
+    CuthillMcKee,
+    /// Cuthill–McKee reversed, which usually reduces the fill-in as well.
+    ReverseCuthillMcKee,
+    /// Morton (Z-order) curve over the node coordinates.
+    Morton,
+}
+
+/// Ids listed in their new order: `order()[i]` is renumbered to `i + base`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Permutation {
+    order: Vec<i32>,
+    base: i32,
+}
+
+impl Permutation {
+    /// Renumbering of the ids in `order` from `base`, `None` if an id is
+    /// listed twice.
+    pub fn new(order: Vec<i32>, base: i32) -> Option<Self> {
+        let mut seen = HashSet::with_capacity(order.len());
+        if !order.iter().all(|id| seen.insert(*id)) {
+            return None;
+        }
+        Some(Self { order, base })
+    }
+
+    /// Old ids, in their new order.
+    pub fn order(&self) -> &[i32] {
+        &self.order
+    }
+
+    /// First id of the renumbering.
+    pub fn base(&self) -> i32 {
+        self.base
+    }
+
+    /// New id of each old id.
+    pub fn map(&self) -> HashMap<i32, i32> {
+        self.order
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| (id, i as i32 + self.base))
+            .collect()
+    }
+}
+
+/// Mesh renumbered contiguously, with the permutations that produced it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Renumbered {
+    mesh: Mesh,
+    nodes: Permutation,
+    elements: Permutation,
+}
+
+impl Renumbered {
+    pub fn mesh(&self) -> &Mesh {
+        &self.mesh
+    }
+
+    pub fn into_mesh(self) -> Mesh {
+        self.mesh
+    }
+
+    pub fn nodes(&self) -> &Permutation {
+        &self.nodes
+    }
+
+    pub fn elements(&self) -> &Permutation {
+        &self.elements
+    }
+}
+
+impl Mesh {
+    /// Order of the nodes according to `reordering`.
+    ///
+    /// Nodes not referenced by any element are placed last, by increasing id.
+    pub fn node_ordering(&self, reordering: Reordering) -> Permutation {
+        let mut order = match reordering {
+            Reordering::Natural => {
+                let mut ids: Vec<node::Id> = self.nodes.keys().copied().collect();
+                ids.sort_unstable();
+                ids
+            }
+            Reordering::CuthillMcKee => cuthill_mckee(&self.node_graph()),
+            Reordering::ReverseCuthillMcKee => {
+                let mut order = cuthill_mckee(&self.node_graph());
+                order.reverse();
+                order
+            }
+            Reordering::Morton => self.morton(),
+        };
+
+        let ordered: HashSet<node::Id> = order.iter().copied().collect();
+        let mut isolated: Vec<node::Id> = self
+            .nodes
+            .keys()
+            .copied()
+            .filter(|id| !ordered.contains(id))
+            .collect();
+        isolated.sort_unstable();
+        order.extend(isolated);
+        order.retain(|id| self.nodes.contains_key(id));
+        Permutation { order, base: 1 }
+    }
+
+    /// Mesh with nodes and elements numbered contiguously from 1, nodes
+    /// following `reordering` and elements sorted by their lowest new node.
+    pub fn reorder(&self, reordering: Reordering) -> Renumbered {
+        let nodes = self.node_ordering(reordering);
+        self.permute(nodes)
+    }
+
+    /// Mesh with nodes numbered following `nodes`, from its base, and elements
+    /// numbered from the same base, sorted by their lowest new node.
+    ///
+    /// Nodes missing from `nodes` are dropped, along with the elements
+    /// referencing them. Returns `None` if `nodes` lists an id that is not a
+    /// node of the mesh, as when it was built for another mesh.
+    pub fn renumber(&self, nodes: Permutation) -> Option<Renumbered> {
+        if nodes.order.iter().any(|id| !self.nodes.contains_key(id)) {
+            return None;
+        }
+        Some(self.permute(nodes))
+    }
+
+    fn permute(&self, nodes: Permutation) -> Renumbered {
+        let node_map = nodes.map();
+
+        let mut elements: Vec<(node::Id, element::Id)> = self
+            .elements
+            .iter()
+            .filter_map(|(id, (_, _, topology))| {
+                let renumbered: Option<Vec<node::Id>> = topology
+                    .nodes()
+                    .iter()
+                    .map(|n| node_map.get(n).copied())
+                    .collect();
+                Some((renumbered?.into_iter().min()?, *id))
+            })
+            .collect();
+        elements.sort_unstable();
+        let elements = Permutation {
+            order: elements.into_iter().map(|(_, id)| id).collect(),
+            base: nodes.base,
+        };
+
+        self.renumbered(nodes, elements)
+    }
+
+    /// Mesh with nodes and elements numbered densely from `base`, usually 0
+    /// or 1, keeping the order of their ids.
+    ///
+    /// Nodes not referenced by any element are dropped.
+    pub fn compact(&self, base: i32) -> Renumbered {
+        let mut nodes: Vec<node::Id> = self
+            .elements
+            .values()
+            .flat_map(|(_, _, topology)| topology.nodes())
+            .filter(|id| self.nodes.contains_key(id))
+            .collect();
+        nodes.sort_unstable();
+        nodes.dedup();
+
+        let mut elements: Vec<element::Id> = self
+            .elements
+            .iter()
+            .filter(|(_, (_, _, topology))| {
+                topology
+                    .nodes()
+                    .iter()
+                    .all(|id| self.nodes.contains_key(id))
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        elements.sort_unstable();
+
+        let nodes = Permutation { order: nodes, base };
+        let elements = Permutation {
+            order: elements,
+            base,
+        };
+        self.renumbered(nodes, elements)
+    }
+
+    fn renumbered(&self, nodes: Permutation, elements: Permutation) -> Renumbered {
+        let node_map = nodes.map();
+
+        let mut ns = mesh::Nodes::new();
+        for (old, new) in node_map.iter() {
+            ns.insert(*new, self.nodes[old]);
+        }
+        let mut es = mesh::Elements::new();
+        for (old, new) in elements.map() {
+            let (physical, elementary, topology) = &self.elements[&old];
+            let topology = topology.map_nodes(|n| node_map[&n]);
+            es.insert(new, (*physical, *elementary, topology));
+        }
+
+        let mesh =
+            Mesh::new(self.format.clone(), ns, es).with_physical_names(self.physical_names.clone());
+        Renumbered {
+            mesh,
+            nodes,
+            elements,
+        }
+    }
+
+    /// Largest difference between the positions of two connected nodes, when
+    /// nodes are sorted by id; the half bandwidth of a nodal operator.
+    pub fn bandwidth(&self) -> usize {
+        let mut ids: Vec<node::Id> = self.nodes.keys().copied().collect();
+        ids.sort_unstable();
+        let position = |id: node::Id| ids.binary_search(&id).ok();
+
+        self.node_graph()
+            .iter()
+            .flat_map(|(a, neighbours)| {
+                neighbours
+                    .iter()
+                    .filter_map(move |&b| Some(position(a)?.abs_diff(position(b)?)))
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn morton(&self) -> Vec<node::Id> {
+        let (lower, upper) = self.nodes.values().fold(
+            ([f64::INFINITY; 3], [f64::NEG_INFINITY; 3]),
+            |(lower, upper), node| {
+                let p = node.coordinates();
+                (
+                    [lower[0].min(p[0]), lower[1].min(p[1]), lower[2].min(p[2])],
+                    [upper[0].max(p[0]), upper[1].max(p[1]), upper[2].max(p[2])],
+                )
+            },
+        );
+
+        let mut keys: Vec<(u64, node::Id)> = self
+            .nodes
+            .iter()
+            .map(|(id, node)| {
+                let p = node.coordinates();
+                let mut key = 0u64;
+                for axis in 0..3 {
+                    let extent = upper[axis] - lower[axis];
+                    let t = if extent > 0.0 {
+                        (p[axis] - lower[axis]) / extent
+                    } else {
+                        0.0
+                    };
+                    let q = (t * ((1 << 21) - 1) as f64) as u64;
+                    for bit in 0..21 {
+                        key |= ((q >> bit) & 1) << (3 * bit + axis);
+                    }
+                }
+                (key, *id)
+            })
+            .collect();
+        keys.sort_unstable();
+        keys.into_iter().map(|(_, id)| id).collect()
+    }
+}
+
+fn cuthill_mckee(graph: &Adjacency) -> Vec<node::Id> {
+    let degree = |id: node::Id| graph.get(id).map_or(0, <[i32]>::len);
+
+    let mut visited: HashSet<node::Id> = HashSet::new();
+    let mut order = Vec::with_capacity(graph.len());
+
+    let mut seeds: Vec<node::Id> = graph.ids().to_vec();
+    seeds.sort_by_key(|&id| (degree(id), id));
+    for seed in seeds {
+        if visited.contains(&seed) {
+            continue;
+        }
+        let start = peripheral(graph, seed);
+
+        let mut queue = VecDeque::from([start]);
+        visited.insert(start);
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            let mut neighbours: Vec<node::Id> = graph
+                .get(id)
+                .unwrap_or_default()
+                .iter()
+                .copied()
+                .filter(|n| !visited.contains(n))
+                .collect();
+            neighbours.sort_by_key(|&n| (degree(n), n));
+            for n in neighbours {
+                visited.insert(n);
+                queue.push_back(n);
+            }
+        }
+    }
+    order
+}
+
+/// Pseudo-peripheral node of the component of `start`, George–Liu heuristic.
+fn peripheral(graph: &Adjacency, start: node::Id) -> node::Id {
+    let degree = |id: node::Id| graph.get(id).map_or(0, <[i32]>::len);
+
+    let mut node = start;
+    let mut eccentricity = 0;
+    loop {
+        let levels = levels(graph, node);
+        let last = levels.last().cloned().unwrap_or_default();
+        let candidate = last
+            .into_iter()
+            .min_by_key(|&n| (degree(n), n))
+            .unwrap_or(node);
+        if levels.len() <= eccentricity || candidate == node {
+            return node;
+        }
+        eccentricity = levels.len();
+        node = candidate;
+    }
+}
+
+/// Breadth first level structure rooted at `root`.
+fn levels(graph: &Adjacency, root: node::Id) -> Vec<Vec<node::Id>> {
+    let mut visited = HashSet::from([root]);
+    let mut levels = vec![vec![root]];
+    loop {
+        let next: Vec<node::Id> = levels[levels.len() - 1]
+            .iter()
+            .flat_map(|&id| graph.get(id).unwrap_or_default())
+            .copied()
+            .filter(|&n| visited.insert(n))
+            .collect();
+        if next.is_empty() {
+            return levels;
+        }
+        levels.push(next);
+    }
+}
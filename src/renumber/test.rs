@@ -0,0 +1,110 @@
+use {
+    super::{Permutation, Reordering},
+    crate::{
+        element::Topology,
+        mesh::{self, Mesh},
+        node::Node,
+    },
+};
+
+// A strip of quadrangles along x, with node ids scattered so that connected
+// nodes are far apart.
+fn strip() -> Mesh {
+    let ids = [40, 3, 17, 90, 8, 61, 25, 77, 12, 55];
+
+    let mut ns = mesh::Nodes::new();
+    for (i, id) in ids.iter().enumerate() {
+        ns.insert(*id, Node::new((i / 2) as f64, (i % 2) as f64, 0.0));
+    }
+    ns.insert(100, Node::new(9.0, 9.0, 9.0));
+
+    let mut es = mesh::Elements::new();
+    for i in 0..4 {
+        let (a, b, c, d) = (ids[2 * i], ids[2 * i + 2], ids[2 * i + 3], ids[2 * i + 1]);
+        es.insert(10 - i as i32, (1, 1, Topology::Quadrangle4(a, b, c, d)));
+    }
+    Mesh::new(None, ns, es)
+}
+
+#[test]
+fn reverse_cuthill_mckee() {
+    let mesh = strip();
+    assert_eq!(mesh.bandwidth(), 9);
+
+    let renumbered = mesh.reorder(Reordering::ReverseCuthillMcKee);
+    let result = renumbered.mesh();
+    assert_eq!(result.bandwidth(), 3);
+    assert_eq!(result.nodes().len(), 11);
+    assert_eq!(renumbered.nodes().order().last(), Some(&100));
+
+    let mut ids: Vec<i32> = result.elements().keys().copied().collect();
+    ids.sort_unstable();
+    assert_eq!(ids, vec![1, 2, 3, 4]);
+    // Elements along the strip stay in order, from either end.
+    let elements = renumbered.elements().map();
+    let ends = [elements[&10], elements[&7]];
+    assert!(ends == [1, 4] || ends == [4, 1]);
+}
+
+#[test]
+fn orderings() {
+    let mesh = strip();
+
+    let natural = mesh.node_ordering(Reordering::Natural);
+    assert_eq!(natural.order()[..3], [3, 8, 12]);
+
+    let cuthill_mckee = mesh.node_ordering(Reordering::CuthillMcKee);
+    let reverse = mesh.node_ordering(Reordering::ReverseCuthillMcKee);
+    let mut reversed = cuthill_mckee.order()[..10].to_vec();
+    reversed.reverse();
+    assert_eq!(reverse.order()[..10], reversed[..]);
+
+    let morton = mesh.reorder(Reordering::Morton);
+    assert_eq!(morton.nodes().order()[..2], [40, 17]);
+    assert!(morton.mesh().bandwidth() <= 4);
+}
+
+#[test]
+fn compact() {
+    let mesh = strip();
+
+    let compacted = mesh.compact(0);
+    let result = compacted.mesh();
+    assert_eq!(result.nodes().len(), 10);
+    assert!(!compacted.nodes().order().contains(&100));
+
+    let nodes = compacted.nodes().map();
+    assert_eq!(nodes[&3], 0);
+    assert_eq!(nodes[&90], 9);
+    assert_eq!(result.nodes()[&0], mesh.nodes()[&3]);
+
+    let elements = compacted.elements().map();
+    assert_eq!(elements[&7], 0);
+    assert_eq!(elements[&10], 3);
+    assert_eq!(
+        result.elements()[&3].2,
+        Topology::Quadrangle4(nodes[&40], nodes[&17], nodes[&90], nodes[&3])
+    );
+
+    let one = mesh.compact(1);
+    assert_eq!(one.nodes().map()[&3], 1);
+    assert_eq!(one.elements().map()[&10], 4);
+}
+
+#[test]
+fn permutation() {
+    let mesh = strip();
+    assert_eq!(Permutation::new(vec![3, 8, 3], 1), None);
+
+    // Nodes left out are dropped along with their elements.
+    let order = vec![40, 3, 17, 90, 55, 12];
+    let renumbered = mesh.renumber(Permutation::new(order, 0).unwrap()).unwrap();
+    let result = renumbered.mesh();
+    assert_eq!(result.nodes().len(), 6);
+    assert_eq!(result.nodes()[&0], mesh.nodes()[&40]);
+    assert_eq!(result.elements().len(), 1);
+    assert_eq!(result.elements()[&0].2, Topology::Quadrangle4(0, 2, 3, 1));
+
+    let foreign = Permutation::new(vec![40, 1000], 1).unwrap();
+    assert_eq!(mesh.renumber(foreign), None);
+}
@@ -0,0 +1,356 @@
+//! Lagrange shape functions on the gmsh reference elements.
+//!
+//! Reference coordinates follow gmsh: lines, quadrangles and hexahedra span
+//! `[-1, 1]`, simplices are the unit simplices, prisms extrude the unit
+//! triangle along `w` in `[-1, 1]` and pyramids have their square base in
+//! `[-1, 1]` at `w = 0` and apex at `w = 1`.
+
+use {
+    crate::{
+        element::Family,
+        geometry::{self, Point},
+    },
+    std::sync::OnceLock,
+};
+
+#[cfg(test)]
+mod test;
+
+/// Highest order of the `Lagrange` elements, whose node ordering follows gmsh.
+const ORDER: usize = 2;
+
+const FAMILIES: [Family; 8] = [
+    Family::Point,
+    Family::Line,
+    Family::Triangle,
+    Family::Quadrangle,
+    Family::Tetrahedron,
+    Family::Hexahedron,
+    Family::Prism,
+    Family::Pyramid,
+];
+
+/// Lagrange shape functions of a reference element, one per node in gmsh
+/// ordering: vertices, then edge nodes, quadrangle face nodes and the interior
+/// node of hexahedra.
+///
+/// Pyramids use the rational space of Bergot, Cohen and Duruflé, as gmsh does.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lagrange {
+    family: Family,
+    order: usize,
+    nodes: Vec<Point>,
+    terms: Vec<Term>,
+    /// Coefficients over `terms` of each shape function.
+    coefficients: Vec<Vec<f64>>,
+}
+
+impl Lagrange {
+    /// Shape functions of the given `order` on the reference element of
+    /// `family`, `None` unless `order` is 1 or 2.
+    pub fn new(family: Family, order: usize) -> Option<Self> {
+        if !(1..=ORDER).contains(&order) {
+            return None;
+        }
+        let nodes = nodes(family, order);
+        let terms = space(family, order as i32);
+        let vandermonde = nodes
+            .iter()
+            .map(|&p| terms.iter().map(|t| t.value(p)).collect())
+            .collect();
+        let inverse = inverse(vandermonde)?;
+        let coefficients = (0..nodes.len())
+            .map(|b| inverse.iter().map(|row| row[b]).collect())
+            .collect();
+        Some(Self {
+            family,
+            order,
+            nodes,
+            terms,
+            coefficients,
+        })
+    }
+
+    pub fn family(&self) -> Family {
+        self.family
+    }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Reference coordinates of the nodes, in gmsh ordering.
+    pub fn nodes(&self) -> &[[f64; 3]] {
+        &self.nodes
+    }
+
+    /// Shape function values at reference coordinates `p`.
+    pub fn values(&self, p: [f64; 3]) -> Vec<f64> {
+        let terms: Vec<f64> = self.terms.iter().map(|t| t.value(p)).collect();
+        self.coefficients
+            .iter()
+            .map(|c| c.iter().zip(&terms).map(|(c, t)| c * t).sum())
+            .collect()
+    }
+
+    /// Shape function gradients with respect to the reference coordinates.
+    pub fn gradients(&self, p: [f64; 3]) -> Vec<[f64; 3]> {
+        let terms: Vec<Point> = self.terms.iter().map(|t| t.gradient(p)).collect();
+        self.coefficients
+            .iter()
+            .map(|c| {
+                c.iter().zip(&terms).fold([0.0; 3], |acc, (c, t)| {
+                    geometry::add(acc, geometry::scale(*t, *c))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Monomial `u^i v^j w^k / (1 - w)^m`, where `m` is only nonzero for the
+/// rational functions of pyramids.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Term {
+    powers: [i32; 3],
+    apex: i32,
+}
+
+impl Term {
+    fn value(&self, p: Point) -> f64 {
+        let [i, j, k] = self.powers;
+        p[0].powi(i) * p[1].powi(j) * p[2].powi(k) / apex(p[2]).powi(self.apex)
+    }
+
+    fn gradient(&self, p: Point) -> Point {
+        let [u, v, w] = p;
+        let [i, j, k] = self.powers;
+        let derivative = |x: f64, n: i32| {
+            if n == 0 {
+                0.0
+            } else {
+                n as f64 * x.powi(n - 1)
+            }
+        };
+        let a = apex(w).powi(self.apex);
+        [
+            derivative(u, i) * v.powi(j) * w.powi(k) / a,
+            u.powi(i) * derivative(v, j) * w.powi(k) / a,
+            u.powi(i) * v.powi(j) * (derivative(w, k) + self.apex as f64 * w.powi(k) / apex(w)) / a,
+        ]
+    }
+}
+
+/// Basis of the polynomial space of the given `order` on `family`.
+fn space(family: Family, order: i32) -> Vec<Term> {
+    let mut terms = Vec::new();
+    for k in 0..=order {
+        for j in 0..=order {
+            for i in 0..=order {
+                let keep = match family {
+                    Family::Point => i + j + k == 0,
+                    Family::Line => j + k == 0,
+                    Family::Triangle => k == 0 && i + j <= order,
+                    Family::Quadrangle => k == 0,
+                    Family::Tetrahedron => i + j + k <= order,
+                    Family::Hexahedron => true,
+                    Family::Prism => i + j <= order,
+                    Family::Pyramid => k <= order - i.max(j),
+                };
+                let apex = match family {
+                    Family::Pyramid => i.min(j),
+                    _ => 0,
+                };
+                if keep {
+                    terms.push(Term {
+                        powers: [i, j, k],
+                        apex,
+                    });
+                }
+            }
+        }
+    }
+    terms
+}
+
+/// Reference coordinates of the nodes of the given `order`, in gmsh ordering.
+fn nodes(family: Family, order: usize) -> Vec<Point> {
+    let vertices = reference(family);
+    let mut nodes = vertices.clone();
+    if order == 2 {
+        let centre = |local: &[usize]| {
+            let points: Vec<Point> = local.iter().map(|&v| vertices[v]).collect();
+            geometry::centroid(&points)
+        };
+        nodes.extend(family.edges().iter().map(|edge| centre(edge)));
+        let quadrangles = family.faces().iter().filter(|face| face.len() == 4);
+        nodes.extend(quadrangles.map(|face| centre(face)));
+        if family == Family::Hexahedron {
+            nodes.push(geometry::centroid(&vertices));
+        }
+    }
+    nodes
+}
+
+/// Inverse of the square matrix `a`, by Gauss-Jordan elimination with partial
+/// pivoting, `None` if it is singular.
+fn inverse(mut a: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let n = a.len();
+    let mut b: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+    for column in 0..n {
+        let pivot =
+            (column..n).max_by(|&i, &j| a[i][column].abs().total_cmp(&a[j][column].abs()))?;
+        if a[pivot][column].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+        let scale = a[column][column];
+        a[column].iter_mut().for_each(|x| *x /= scale);
+        b[column].iter_mut().for_each(|x| *x /= scale);
+        let (pivot_a, pivot_b) = (a[column].clone(), b[column].clone());
+        for row in (0..n).filter(|&row| row != column) {
+            let factor = a[row][column];
+            for (x, p) in a[row].iter_mut().zip(&pivot_a) {
+                *x -= factor * p;
+            }
+            for (x, p) in b[row].iter_mut().zip(&pivot_b) {
+                *x -= factor * p;
+            }
+        }
+    }
+    Some(b)
+}
+
+/// Vertices of the reference element, in gmsh ordering.
+pub(crate) fn reference(family: Family) -> Vec<Point> {
+    match family {
+        Family::Point => vec![[0.0, 0.0, 0.0]],
+        Family::Line => vec![[-1.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+        Family::Triangle => vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        Family::Quadrangle => vec![
+            [-1.0, -1.0, 0.0],
+            [1.0, -1.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [-1.0, 1.0, 0.0],
+        ],
+        Family::Tetrahedron => vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ],
+        Family::Hexahedron => vec![
+            [-1.0, -1.0, -1.0],
+            [1.0, -1.0, -1.0],
+            [1.0, 1.0, -1.0],
+            [-1.0, 1.0, -1.0],
+            [-1.0, -1.0, 1.0],
+            [1.0, -1.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [-1.0, 1.0, 1.0],
+        ],
+        Family::Prism => vec![
+            [0.0, 0.0, -1.0],
+            [1.0, 0.0, -1.0],
+            [0.0, 1.0, -1.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [0.0, 1.0, 1.0],
+        ],
+        Family::Pyramid => vec![
+            [-1.0, -1.0, 0.0],
+            [1.0, -1.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [-1.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ],
+    }
+}
+
+/// Whether `p` lies in the reference element, up to `tolerance`.
+pub(crate) fn contains(family: Family, p: Point, tolerance: f64) -> bool {
+    let [u, v, w] = p;
+    let unit = |x: f64| x.abs() <= 1.0 + tolerance;
+    let positive = |x: f64| x >= -tolerance;
+    match family {
+        Family::Point => true,
+        Family::Line => unit(u),
+        Family::Triangle => positive(u) && positive(v) && u + v <= 1.0 + tolerance,
+        Family::Quadrangle => unit(u) && unit(v),
+        Family::Tetrahedron => {
+            positive(u) && positive(v) && positive(w) && u + v + w <= 1.0 + tolerance
+        }
+        Family::Hexahedron => unit(u) && unit(v) && unit(w),
+        Family::Prism => positive(u) && positive(v) && u + v <= 1.0 + tolerance && unit(w),
+        Family::Pyramid => {
+            positive(w)
+                && w <= 1.0 + tolerance
+                && u.abs() <= 1.0 - w + tolerance
+                && v.abs() <= 1.0 - w + tolerance
+        }
+    }
+}
+
+/// `p` moved into the reference element, absorbing the rounding of reference
+/// coordinates computed for points on its boundary.
+pub(crate) fn clamp(family: Family, p: Point) -> Point {
+    let [u, v, w] = p;
+    let unit = |x: f64| x.clamp(-1.0, 1.0);
+    match family {
+        Family::Point => [0.0; 3],
+        Family::Line => [unit(u), 0.0, 0.0],
+        Family::Quadrangle => [unit(u), unit(v), 0.0],
+        Family::Hexahedron => [unit(u), unit(v), unit(w)],
+        Family::Triangle => {
+            let [u, v] = simplex([u, v]);
+            [u, v, 0.0]
+        }
+        Family::Tetrahedron => simplex([u, v, w]),
+        Family::Prism => {
+            let [u, v] = simplex([u, v]);
+            [u, v, unit(w)]
+        }
+        Family::Pyramid => {
+            let w = w.clamp(0.0, 1.0);
+            [u.clamp(w - 1.0, 1.0 - w), v.clamp(w - 1.0, 1.0 - w), w]
+        }
+    }
+}
+
+/// Projection onto the unit simplex `x >= 0, sum(x) <= 1`.
+fn simplex<const N: usize>(mut x: [f64; N]) -> [f64; N] {
+    for _ in 0..N {
+        x = x.map(|c| c.max(0.0));
+        let excess = x.iter().sum::<f64>() - 1.0;
+        if excess <= 0.0 {
+            break;
+        }
+        let active = x.iter().filter(|&&c| c > 0.0).count() as f64;
+        x = x.map(|c| if c > 0.0 { c - excess / active } else { c });
+    }
+    x.map(|c| c.max(0.0))
+}
+
+/// First order shape functions of `family`, one per vertex, built once.
+pub(crate) fn linear(family: Family) -> &'static Lagrange {
+    static LINEAR: OnceLock<Vec<Lagrange>> = OnceLock::new();
+    let all = LINEAR.get_or_init(|| {
+        FAMILIES
+            .iter()
+            .map(|&family| Lagrange::new(family, 1).expect("first order is supported"))
+            .collect()
+    });
+    let index = FAMILIES
+        .iter()
+        .position(|&f| f == family)
+        .unwrap_or_default();
+    &all[index]
+}
+
+/// Distance to the apex of the pyramid, kept away from zero where the
+/// rational shape functions are singular.
+fn apex(w: f64) -> f64 {
+    (1.0 - w).max(1e-12)
+}
@@ -0,0 +1,80 @@
+use crate::{
+    element::Family,
+    shape::{self, Lagrange, FAMILIES},
+};
+
+#[test]
+fn nodes() {
+    let count = |family, order| Lagrange::new(family, order).unwrap().nodes().len();
+    let second = FAMILIES.map(|family| count(family, 2));
+    assert_eq!(second, [1, 3, 6, 9, 10, 27, 18, 14]);
+    assert_eq!(Lagrange::new(Family::Triangle, 0), None);
+    assert_eq!(Lagrange::new(Family::Triangle, 3), None);
+
+    // Edge nodes follow the vertices, in the order of the edges.
+    let tetrahedron = Lagrange::new(Family::Tetrahedron, 2).unwrap();
+    assert_eq!(tetrahedron.nodes()[4], [0.5, 0.0, 0.0]);
+    assert_eq!(tetrahedron.nodes()[8], [0.0, 0.5, 0.5]);
+    let hexahedron = Lagrange::new(Family::Hexahedron, 2).unwrap();
+    assert_eq!(hexahedron.nodes()[20], [0.0, 0.0, -1.0]);
+    assert_eq!(hexahedron.nodes()[26], [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn interpolation() {
+    for family in FAMILIES {
+        for order in 1..=2 {
+            let lagrange = Lagrange::new(family, order).unwrap();
+            let nodes = lagrange.nodes();
+            for (a, &node) in nodes.iter().enumerate() {
+                for (b, value) in lagrange.values(node).into_iter().enumerate() {
+                    let expected = if a == b { 1.0 } else { 0.0 };
+                    assert!((value - expected).abs() < 1e-9, "{family:?} {order}");
+                }
+            }
+
+            let p = [0.2, 0.1, 0.3];
+            let sum: f64 = lagrange.values(p).iter().sum();
+            assert!((sum - 1.0).abs() < 1e-12);
+            // Gradients against central differences.
+            let h = 1e-6;
+            let gradients = lagrange.gradients(p);
+            for axis in 0..family.dimension() as usize {
+                let (mut forward, mut backward) = (p, p);
+                forward[axis] += h;
+                backward[axis] -= h;
+                let forward = lagrange.values(forward);
+                let difference = forward
+                    .iter()
+                    .zip(lagrange.values(backward))
+                    .map(|(f, b)| (f - b) / (2.0 * h));
+                for (g, d) in gradients.iter().zip(difference) {
+                    assert!((g[axis] - d).abs() < 1e-6, "{family:?} {order}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn first_order() {
+    let p = [0.2, -0.1, 0.3];
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+    for family in FAMILIES {
+        let lagrange = Lagrange::new(family, 1).unwrap();
+        assert_eq!(shape::linear(family), &lagrange);
+    }
+
+    // Closed forms of the first vertex of each kind of element.
+    let value = |family| shape::linear(family).values(p)[0];
+    assert!(close(value(Family::Line), 0.4));
+    assert!(close(value(Family::Tetrahedron), 0.6));
+    assert!(close(value(Family::Quadrangle), 0.8 * 1.1 / 4.0));
+    assert!(close(value(Family::Hexahedron), 0.8 * 1.1 * 0.7 / 8.0));
+    assert!(close(value(Family::Prism), 0.9 * 0.7 / 2.0));
+    assert!(close(value(Family::Pyramid), 0.5 * 0.8 / 2.8));
+    assert!(close(shape::linear(Family::Pyramid).values(p)[4], 0.3));
+
+    let gradient = shape::linear(Family::Quadrangle).gradients(p)[0];
+    assert!(close(gradient[0], -1.1 / 4.0) && close(gradient[1], -0.8 / 4.0));
+}
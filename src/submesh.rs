@@ -0,0 +1,136 @@
+//! Extraction of a subset of the elements of a mesh.
+
+use {
+    crate::{
+        element::{self, Elementary, Family, Physical},
+        mesh::{self, Mesh},
+        node,
+    },
+    std::collections::{BTreeSet, HashMap},
+};
+
+#[cfg(test)]
+mod test;
+
+type Predicate<'a> = Box<dyn Fn(&Mesh, element::Id) -> bool + 'a>;
+
+/// Selection of the elements kept in a submesh.
+pub enum Filter<'a> {
+    Physical(Vec<Physical>),
+    Elementary(Vec<Elementary>),
+    Family(Vec<Family>),
+    Predicate(Predicate<'a>),
+}
+
+impl<'a> Filter<'a> {
+    pub fn predicate<F: Fn(&Mesh, element::Id) -> bool + 'a>(f: F) -> Self {
+        Self::Predicate(Box::new(f))
+    }
+
+    fn keeps(&self, mesh: &Mesh, id: element::Id) -> bool {
+        let (physical, elementary, topology) = &mesh.elements[&id];
+        match self {
+            Self::Physical(tags) => tags.contains(physical),
+            Self::Elementary(tags) => tags.contains(elementary),
+            Self::Family(families) => families.contains(&topology.family()),
+            Self::Predicate(f) => f(mesh, id),
+        }
+    }
+}
+
+/// Mesh extracted from a parent, with maps from its ids to the parent ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submesh {
+    mesh: Mesh,
+    nodes: HashMap<node::Id, node::Id>,
+    elements: HashMap<element::Id, element::Id>,
+}
+
+impl Submesh {
+    pub fn mesh(&self) -> &Mesh {
+        &self.mesh
+    }
+
+    pub fn into_mesh(self) -> Mesh {
+        self.mesh
+    }
+
+    /// Parent id of each node of the submesh.
+    pub fn nodes(&self) -> &HashMap<node::Id, node::Id> {
+        &self.nodes
+    }
+
+    /// Parent id of each element of the submesh.
+    pub fn elements(&self) -> &HashMap<element::Id, element::Id> {
+        &self.elements
+    }
+}
+
+impl Mesh {
+    /// Elements selected by `filter`, together with the nodes they reference.
+    ///
+    /// When `renumber` is set, nodes and elements are numbered contiguously
+    /// from 1, following the order of their parent ids; otherwise parent ids
+    /// are kept. Elements over nodes missing from the mesh are left out.
+    pub fn submesh(&self, filter: &Filter, renumber: bool) -> Submesh {
+        let elements: BTreeSet<element::Id> = self
+            .elements
+            .keys()
+            .copied()
+            .filter(|&id| filter.keeps(self, id))
+            .filter(|id| {
+                let (.., topology) = &self.elements[id];
+                topology.nodes().iter().all(|n| self.nodes.contains_key(n))
+            })
+            .collect();
+        let nodes: BTreeSet<node::Id> = elements
+            .iter()
+            .flat_map(|id| self.elements[id].2.nodes())
+            .collect();
+
+        let numbering = |ids: &BTreeSet<i32>| -> HashMap<i32, i32> {
+            ids.iter()
+                .enumerate()
+                .map(|(i, &id)| {
+                    if renumber {
+                        (id, i as i32 + 1)
+                    } else {
+                        (id, id)
+                    }
+                })
+                .collect()
+        };
+        let node_ids = numbering(&nodes);
+        let element_ids = numbering(&elements);
+
+        let mut ns = mesh::Nodes::new();
+        for (old, new) in node_ids.iter() {
+            ns.insert(*new, self.nodes[old]);
+        }
+        let mut es = mesh::Elements::new();
+        for (old, new) in element_ids.iter() {
+            let (physical, elementary, topology) = &self.elements[old];
+            let topology = topology.map_nodes(|id| node_ids[&id]);
+            es.insert(*new, (*physical, *elementary, topology));
+        }
+
+        let physical_names = self
+            .physical_names
+            .iter()
+            .filter(|((dimension, tag), _)| {
+                es.values()
+                    .any(|(p, _, t)| p == tag && t.dimension() == *dimension)
+            })
+            .map(|(key, name)| (*key, name.clone()))
+            .collect();
+
+        Submesh {
+            mesh: Mesh::new(self.format.clone(), ns, es).with_physical_names(physical_names),
+            nodes: node_ids.into_iter().map(|(old, new)| (new, old)).collect(),
+            elements: element_ids
+                .into_iter()
+                .map(|(old, new)| (new, old))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use {
+    super::Filter,
+    crate::{
+        element::{Family, Topology},
+        mesh::{self, Mesh},
+        node::Node,
+    },
+};
+
+fn mesh() -> Mesh {
+    let mut ns = mesh::Nodes::new();
+    ns.insert(10, Node::new(-1.0, 0.0, 0.0));
+    ns.insert(20, Node::new(0.0, 0.0, 0.0));
+    ns.insert(30, Node::new(0.0, 1.0, 0.0));
+    ns.insert(40, Node::new(1.0, 0.0, 0.0));
+    ns.insert(50, Node::new(5.0, 5.0, 5.0));
+
+    let mut es = mesh::Elements::new();
+    es.insert(7, (1, 3, Topology::Triangle3(10, 20, 30)));
+    es.insert(9, (2, 4, Topology::Triangle3(20, 40, 30)));
+    es.insert(11, (3, 5, Topology::Line2(20, 30)));
+
+    Mesh::new(None, ns, es)
+}
+
+#[test]
+fn physical() {
+    let submesh = mesh().submesh(&Filter::Physical(vec![2]), true);
+
+    let mut ns = mesh::Nodes::new();
+    ns.insert(1, Node::new(0.0, 0.0, 0.0));
+    ns.insert(2, Node::new(0.0, 1.0, 0.0));
+    ns.insert(3, Node::new(1.0, 0.0, 0.0));
+    let mut es = mesh::Elements::new();
+    es.insert(1, (2, 4, Topology::Triangle3(1, 3, 2)));
+
+    assert_eq!(submesh.mesh(), &Mesh::new(None, ns, es));
+    assert_eq!(submesh.nodes()[&3], 40);
+    assert_eq!(submesh.elements()[&1], 9);
+}
+
+#[test]
+fn predicate() {
+    let parent = mesh();
+
+    let filter = Filter::predicate(|mesh, id| mesh.centroid(id).unwrap()[0] < 0.0);
+    let submesh = parent.submesh(&filter, false);
+    assert_eq!(submesh.mesh().elements().len(), 1);
+    assert_eq!(submesh.mesh().nodes().len(), 3);
+    assert_eq!(submesh.elements()[&7], 7);
+
+    let lines = parent.submesh(&Filter::Family(vec![Family::Line]), false);
+    assert_eq!(lines.mesh().elements()[&11].2, Topology::Line2(20, 30));
+
+    let none = parent.submesh(&Filter::Elementary(vec![42]), true);
+    assert!(none.mesh().nodes().is_empty());
+}
+
+#[test]
+fn missing() {
+    // Node 1 would be a new id; the element over it must not point there.
+    let mut parent = mesh();
+    parent.elements.insert(13, (2, 4, Topology::Line2(40, 1)));
+
+    let submesh = parent.submesh(&Filter::Physical(vec![2]), true);
+    assert_eq!(submesh.mesh().elements().len(), 1);
+    assert_eq!(submesh.elements()[&1], 9);
+    assert_eq!(submesh.mesh().nodes().len(), 3);
+}